@@ -1,17 +1,90 @@
-﻿use crate::game::data::pixel_simulation::{Chunks, Particles};
+﻿use bevy::prelude::*;
+use crate::game::data::pixel_simulation::{Chunks, ChunkIndex, Particles, CellType};
+use crate::game::data::wind_field::WindField;
+use crate::game::data::simulation_action::SimulationAction;
+use crate::game::constants::{PARTICLE_GRAVITY, WIND_PARTICLE_COUPLING};
 
 pub struct MainCamera;
 
+pub struct DebugOverlayPart;
+
+pub struct ChunkActivityHeatmap {
+    pub chunk_index: ChunkIndex
+}
+
+pub struct ChunkDebugLabel {
+    pub chunk_index: ChunkIndex
+}
+
 pub struct PixelSimulation {
     pub chunks: Chunks,
-    pub particles: Particles
+    pub particles: Particles,
+    pub wind_field: WindField,
+    pub lost_particle_count: u64,
+    pub iteration: u64,
+    pub moved_cell_counts: [u32; 9],
+    pub gravity: f32,
+    pub wind_particle_coupling: f32,
+    // Set by the inspector, which can't reach the `ActionQueue`, and moved there by `queue_inspector_actions`.
+    pub inspector_action: Option<SimulationAction>
 }
 
 impl PixelSimulation {
     pub fn new(chunks: Chunks) -> Self {
         Self {
             chunks,
-            particles: Particles::new()
+            particles: Particles::new(),
+            wind_field: WindField::new(),
+            lost_particle_count: 0,
+            iteration: 0,
+            moved_cell_counts: [0; 9],
+            gravity: PARTICLE_GRAVITY,
+            wind_particle_coupling: WIND_PARTICLE_COUPLING,
+            inspector_action: None
+        }
+    }
+}
+
+pub struct WindSource {
+    pub velocity: Vec2,
+    pub radius: f32
+}
+
+pub struct CellEmitter {
+    pub cell_type: CellType,
+    pub rate: f32,
+    pub velocity: Vec2,
+    pub cone_angle: f32,
+    pub spread: f32,
+    pending_emissions: f32
+}
+
+impl CellEmitter {
+    pub fn new(cell_type: CellType, rate: f32, velocity: Vec2, cone_angle: f32, spread: f32) -> Self {
+        Self {
+            cell_type,
+            rate,
+            velocity,
+            cone_angle,
+            spread,
+            pending_emissions: 0.
         }
     }
+
+    pub fn take_emission_count(&mut self, delta_seconds: f32) -> u32 {
+        self.pending_emissions += self.rate * delta_seconds;
+
+        let emission_count = self.pending_emissions.floor();
+        self.pending_emissions -= emission_count;
+
+        emission_count as u32
+    }
+
+    pub fn reset_pending_emissions(&mut self) {
+        self.pending_emissions = 0.;
+    }
+}
+
+pub struct CellDrain {
+    pub radius: f32
 }
\ No newline at end of file
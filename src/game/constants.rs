@@ -1,3 +1,50 @@
-﻿pub const CHUNK_SIZE: usize = 64;
+﻿use bevy::diagnostic::DiagnosticId;
+
+pub const CHUNK_SIZE: usize = 64;
+// The world is a 3x3 grid of chunks, this is its width and height in cells.
+pub const WORLD_SIZE: usize = 3 * CHUNK_SIZE;
 pub const WORLD_CHUNK_SIZE: f32 = 300.0;
 pub const PIXEL_SIMULATION_TIMESTEP: f32 = 1.0 / 60.0;
+pub const MIN_SIMULATION_SPEED: f32 = 0.125;
+pub const MAX_SIMULATION_SPEED: f32 = 8.0;
+// Enough ticks to run at the maximum speed when frames take one timestep, plus one tick of slack for frame jitter.
+pub const DEFAULT_MAX_TICKS_PER_FRAME: u32 = MAX_SIMULATION_SPEED as u32 + 1;
+pub const MAX_EDIT_HISTORY_LENGTH: usize = 32;
+pub const WORLD_FILE_PATH: &str = "world.pxsim";
+pub const INPUT_BINDINGS_PATH: &str = "assets/config/input_bindings.cfg";
+pub const IMPORT_PALETTE_PATH: &str = "assets/palettes/default.palette";
+pub const EXPORT_IMAGE_PATH: &str = "world.png";
+pub const EXPORT_MATERIAL_IDS_PATH: &str = "world_materials.png";
+pub const RECORDING_WORLD_PATH: &str = "recording.pxsim";
+pub const RECORDING_ACTIONS_PATH: &str = "recording.actions";
+pub const FRAME_CAPTURE_DIRECTORY: &str = "captures";
+pub const FRAME_CAPTURE_GIF_NAME: &str = "capture.gif";
+// NeuQuant sampling speed from 1 (best, slowest) to 30, frames are quantized on the main thread while capturing.
+pub const FRAME_CAPTURE_GIF_SPEED: i32 = 10;
+pub const DEFAULT_FRAME_CAPTURE_INTERVAL: u64 = 4;
+pub const DEFAULT_BRUSH_RADIUS: u32 = 2;
+pub const MAX_BRUSH_RADIUS: u32 = 32;
+pub const BRUSH_PARTICLE_DENSITY: f32 = 0.3;
+pub const SHAPE_PREVIEW_ALPHA: u8 = 160;
+pub const DEBUG_OVERLAY_BORDER_WIDTH: f32 = 2.0;
+pub const DEBUG_OVERLAY_FONT_SIZE: f32 = 16.0;
+pub const DEBUG_OVERLAY_HEATMAP_MAX_ALPHA: f32 = 0.5;
+pub const DEBUG_OVERLAY_HEATMAP_SATURATED_CELL_COUNT: u32 = 1024;
+pub const CAMERA_MIN_SCALE: f32 = 0.1;
+pub const CAMERA_MAX_SCALE: f32 = 3.0;
+pub const CAMERA_ZOOM_STEP: f32 = 1.15;
+pub const CAMERA_KEYBOARD_PAN_SPEED: f32 = 600.0;
+pub const PARTICLE_GRAVITY: f32 = 200.0;
+pub const PARTICLE_DEPOSITION_RADIUS: i32 = 4;
+pub const PARTICLE_BOUNCE_MIN_SPEED: f32 = 20.0;
+pub const DEFAULT_MAX_PARTICLE_COUNT: usize = 20000;
+
+pub const PARTICLE_COUNT_DIAGNOSTIC: DiagnosticId = DiagnosticId::from_u128(151840569264862184839261536389120546671);
+pub const SKIPPED_TICKS_DIAGNOSTIC: DiagnosticId = DiagnosticId::from_u128(260813094581375212873946062201334089017);
+
+pub const WIND_FIELD_CELL_SIZE: usize = 8;
+pub const WIND_FIELD_DECAY: f32 = 0.9;
+pub const WIND_PARTICLE_COUPLING: f32 = 0.5;
+pub const WIND_LIFT_THRESHOLD: f32 = 40.0;
+pub const WIND_LIFT_CHANCE: f32 = 0.0005;
+pub const WIND_DRIFT_THRESHOLD: f32 = 10.0;
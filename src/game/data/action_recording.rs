@@ -0,0 +1,47 @@
+﻿use crate::game::data::simulation_action::SimulationAction;
+
+#[derive(Clone)]
+pub struct ActionRecording {
+    pub seed: u64,
+    pub actions: Vec<(u64, SimulationAction)>
+}
+
+impl ActionRecording {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            actions: Vec::new()
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("seed {}\n", self.seed);
+
+        for (tick, action) in &self.actions {
+            text.push_str(&format!("{} {}\n", tick, action.to_line()));
+        }
+
+        text
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut lines = text.lines();
+        let seed = lines.next()?.strip_prefix("seed ")?.trim().parse().ok()?;
+
+        let actions = lines
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let mut parts = line.splitn(2, ' ');
+                let tick = parts.next()?.parse().ok()?;
+                let action = SimulationAction::from_line(parts.next()?)?;
+
+                Some((tick, action))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            seed,
+            actions
+        })
+    }
+}
\ No newline at end of file
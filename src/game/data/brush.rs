@@ -0,0 +1,41 @@
+﻿use bevy::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BrushShape {
+    Circle,
+    Square
+}
+
+impl BrushShape {
+    pub fn name(self) -> &'static str {
+        match self {
+            BrushShape::Circle => "circle",
+            BrushShape::Square => "square"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "circle" => Some(BrushShape::Circle),
+            "square" => Some(BrushShape::Square),
+            _ => None
+        }
+    }
+
+    pub fn contains(self, offset: IVec2, radius: u32) -> bool {
+        let radius = radius as i32;
+
+        match self {
+            BrushShape::Circle => offset.x * offset.x + offset.y * offset.y <= radius * (radius + 1),
+            BrushShape::Square => offset.x.abs() <= radius && offset.y.abs() <= radius
+        }
+    }
+
+    pub fn offsets(self, radius: u32) -> impl Iterator<Item = IVec2> {
+        let extent = radius as i32;
+
+        (-extent..=extent)
+            .flat_map(move |x| (-extent..=extent).map(move |y| IVec2::new(x, y)))
+            .filter(move |offset| self.contains(*offset, radius))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+﻿use crate::game::data::pixel_simulation::CellType;
+use rand::Rng;
+use lazy_static::lazy_static;
+use palette::{Lch, Gradient, FromColor, Srgba};
+
+lazy_static! {
+    static ref SAND_GRADIENT: Gradient<Lch> = Gradient::new(vec![
+        Lch::new(78.0, 25.0, 92.0),
+        Lch::new(83.0, 25.0, 92.0)
+    ]);
+
+    static ref WATER_GRADIENT: Gradient<Lch> = Gradient::new(vec![
+        Lch::new(65.0, 37.0, 249.0),
+        Lch::new(70.0, 37.0, 249.0)
+    ]);
+}
+
+fn cell_gradient(cell_type: CellType) -> &'static Gradient<Lch> {
+    match cell_type {
+        CellType::Sand => &*SAND_GRADIENT,
+        CellType::Water => &*WATER_GRADIENT
+    }
+}
+
+pub fn random_cell_color<R: Rng>(cell_type: CellType, rng: &mut R) -> Srgba<u8> {
+    let color = cell_gradient(cell_type).get(rng.gen_range(0.0 .. 1.0));
+
+    Srgba::from_color(color).into_format()
+}
+
+pub fn cell_swatch_color(cell_type: CellType) -> Srgba<u8> {
+    let color = cell_gradient(cell_type).get(0.5);
+
+    Srgba::from_color(color).into_format()
+}
@@ -0,0 +1,66 @@
+﻿use bevy::prelude::*;
+use crate::game::data::pixel_simulation::WorldCellPosition;
+
+pub struct CellTraversal {
+    current: IVec2,
+    end: IVec2,
+    step: IVec2,
+    t_max: Vec2,
+    t_delta: Vec2,
+    finished: bool
+}
+
+impl CellTraversal {
+    pub fn new(from: Vec2, to: Vec2) -> Self {
+        let direction = to - from;
+        let current = from.floor().as_i32();
+
+        let axis_setup = |from: f32, current: i32, direction: f32| -> (i32, f32, f32) {
+            if direction > 0. {
+                let t_delta = 1. / direction;
+                (1, (current as f32 + 1. - from) * t_delta, t_delta)
+            } else if direction < 0. {
+                let t_delta = 1. / -direction;
+                (-1, (from - current as f32) * t_delta, t_delta)
+            } else {
+                (0, f32::INFINITY, f32::INFINITY)
+            }
+        };
+
+        let (step_x, t_max_x, t_delta_x) = axis_setup(from.x, current.x, direction.x);
+        let (step_y, t_max_y, t_delta_y) = axis_setup(from.y, current.y, direction.y);
+
+        Self {
+            current,
+            end: to.floor().as_i32(),
+            step: IVec2::new(step_x, step_y),
+            t_max: Vec2::new(t_max_x, t_max_y),
+            t_delta: Vec2::new(t_delta_x, t_delta_y),
+            finished: false
+        }
+    }
+}
+
+impl Iterator for CellTraversal {
+    type Item = WorldCellPosition;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let cell_position = self.current;
+
+        if self.current == self.end || self.t_max.x.min(self.t_max.y) > 1. {
+            self.finished = true;
+        } else if self.t_max.x < self.t_max.y {
+            self.current.x += self.step.x;
+            self.t_max.x += self.t_delta.x;
+        } else {
+            self.current.y += self.step.y;
+            self.t_max.y += self.t_delta.y;
+        }
+
+        Some(WorldCellPosition(cell_position))
+    }
+}
@@ -0,0 +1,118 @@
+﻿use bevy::prelude::*;
+use crate::game::constants::{CHUNK_SIZE, WORLD_CHUNK_SIZE};
+
+fn world_cell_offset() -> Vec2 {
+    Vec2::ONE * (CHUNK_SIZE as f32 * 1.5)
+}
+
+pub fn screen_to_world(window: &Window, camera_transform: &GlobalTransform, screen_position: Vec2) -> Vec3 {
+    let window_size = Vec2::new(window.width(), window.height());
+    let centered_position = screen_position - window_size / 2.0;
+
+    camera_transform.compute_matrix().transform_point3(centered_position.extend(0.0))
+}
+
+pub fn world_to_screen(window: &Window, camera_transform: &GlobalTransform, world_position: Vec3) -> Vec2 {
+    let window_size = Vec2::new(window.width(), window.height());
+    let centered_position = camera_transform.compute_matrix().inverse().transform_point3(world_position);
+
+    Vec2::new(centered_position.x, centered_position.y) + window_size / 2.0
+}
+
+pub fn world_to_cell(pixel_simulation_transform: &GlobalTransform, world_position: Vec3) -> Vec2 {
+    let local_position = pixel_simulation_transform.compute_matrix().inverse().transform_point3(world_position);
+
+    Vec2::new(local_position.x, -local_position.y) / WORLD_CHUNK_SIZE * CHUNK_SIZE as f32 + world_cell_offset()
+}
+
+pub fn cell_to_world(pixel_simulation_transform: &GlobalTransform, cell_position: Vec2) -> Vec3 {
+    let local_position = (cell_position - world_cell_offset()) / CHUNK_SIZE as f32 * WORLD_CHUNK_SIZE;
+
+    pixel_simulation_transform.compute_matrix().transform_point3(Vec3::new(local_position.x, -local_position.y, 0.0))
+}
+
+pub fn screen_to_cell(window: &Window, camera_transform: &GlobalTransform, pixel_simulation_transform: &GlobalTransform, screen_position: Vec2) -> Vec2 {
+    world_to_cell(pixel_simulation_transform, screen_to_world(window, camera_transform, screen_position))
+}
+
+pub fn cell_to_screen(window: &Window, camera_transform: &GlobalTransform, pixel_simulation_transform: &GlobalTransform, cell_position: Vec2) -> Vec2 {
+    world_to_screen(window, camera_transform, cell_to_world(pixel_simulation_transform, cell_position))
+}
+
+pub fn cursor_to_cell(window: &Window, camera_transform: &GlobalTransform, pixel_simulation_transform: &GlobalTransform) -> Option<Vec2> {
+    let cursor_position = window.cursor_position()?;
+
+    Some(screen_to_cell(window, camera_transform, pixel_simulation_transform, cursor_position))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::window::WindowId;
+    use crate::game::constants::WORLD_SIZE;
+
+    const TOLERANCE: f32 = 0.01;
+
+    fn test_window() -> Window {
+        Window::new(WindowId::primary(), &WindowDescriptor::default(), 800, 600, 1.0, None)
+    }
+
+    fn test_camera_transform() -> GlobalTransform {
+        GlobalTransform {
+            scale: Vec3::splat(2.5),
+            ..GlobalTransform::from_xyz(120.0, -80.0, 999.0)
+        }
+    }
+
+    fn test_pixel_simulation_transform() -> GlobalTransform {
+        GlobalTransform {
+            scale: Vec3::splat(0.75),
+            ..GlobalTransform::from_xyz(-40.0, 60.0, 0.0)
+        }
+    }
+
+    #[test]
+    fn round_trips_cells_through_the_screen() {
+        let window = test_window();
+        let camera_transform = test_camera_transform();
+        let pixel_simulation_transform = test_pixel_simulation_transform();
+
+        for &cell_position in &[Vec2::ZERO, Vec2::new(12.5, 170.25), Vec2::ONE * WORLD_SIZE as f32] {
+            let screen_position = cell_to_screen(&window, &camera_transform, &pixel_simulation_transform, cell_position);
+            let round_tripped = screen_to_cell(&window, &camera_transform, &pixel_simulation_transform, screen_position);
+
+            assert!((round_tripped - cell_position).length() < TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn round_trips_screen_positions_through_cells() {
+        let window = test_window();
+        let camera_transform = test_camera_transform();
+        let pixel_simulation_transform = test_pixel_simulation_transform();
+
+        for &screen_position in &[Vec2::ZERO, Vec2::new(400.0, 300.0), Vec2::new(731.0, 42.5)] {
+            let cell_position = screen_to_cell(&window, &camera_transform, &pixel_simulation_transform, screen_position);
+            let round_tripped = cell_to_screen(&window, &camera_transform, &pixel_simulation_transform, cell_position);
+
+            assert!((round_tripped - screen_position).length() < TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn maps_the_first_cell_to_the_top_left_world_corner() {
+        let pixel_simulation_transform = test_pixel_simulation_transform();
+        let world_position = cell_to_world(&pixel_simulation_transform, Vec2::ZERO);
+        let expected_position = Vec3::new(-40.0, 60.0, 0.0) + Vec3::new(-1.5, 1.5, 0.0) * WORLD_CHUNK_SIZE * 0.75;
+
+        assert!((world_position - expected_position).length() < TOLERANCE);
+    }
+
+    #[test]
+    fn maps_the_screen_center_to_the_camera_position() {
+        let window = test_window();
+        let world_position = screen_to_world(&window, &test_camera_transform(), Vec2::new(400.0, 300.0));
+
+        assert!((world_position - Vec3::new(120.0, -80.0, 999.0)).length() < TOLERANCE);
+    }
+}
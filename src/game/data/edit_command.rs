@@ -0,0 +1,78 @@
+﻿use bevy::prelude::*;
+use crate::game::data::pixel_simulation::{Cell, ChunkCellPosition, ChunkIndex, Chunks, WorldCellPosition};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy)]
+pub struct CellEdit {
+    pub cell_position: ChunkCellPosition,
+    pub before: Option<Cell>,
+    pub after: Option<Cell>
+}
+
+pub struct ChunkEdit {
+    pub chunk_index: ChunkIndex,
+    pub cell_edits: HashMap<usize, CellEdit>
+}
+
+#[derive(Default)]
+pub struct EditCommand {
+    pub chunk_edits: Vec<ChunkEdit>
+}
+
+impl EditCommand {
+    pub fn is_empty(&self) -> bool {
+        self.chunk_edits.iter().all(|chunk_edit| chunk_edit.cell_edits.is_empty())
+    }
+
+    pub fn set_cell_at(&mut self, chunks: &mut Chunks, world_cell_position: WorldCellPosition, cell: Option<Cell>, textures: &mut Assets<Texture>) {
+        let (chunk_index, cell_position) = match world_cell_position.to_chunk_cell_position() {
+            Some(chunk_cell_position) => chunk_cell_position,
+            None => return
+        };
+
+        let chunk_edit = match self.chunk_edits.iter().position(|chunk_edit| *chunk_edit.chunk_index == *chunk_index) {
+            Some(index) => &mut self.chunk_edits[index],
+            None => {
+                self.chunk_edits.push(ChunkEdit {
+                    chunk_index,
+                    cell_edits: HashMap::new()
+                });
+
+                self.chunk_edits.last_mut().unwrap()
+            }
+        };
+
+        let chunk = chunks.get_chunk(chunk_index);
+        let before = chunk.get_cell(cell_position);
+
+        chunk_edit.cell_edits.entry(cell_position.to_cell_index())
+            .or_insert(CellEdit {
+                cell_position,
+                before,
+                after: before
+            })
+            .after = cell;
+
+        chunk.set_cell(cell_position, cell, textures);
+    }
+
+    pub fn undo(&self, chunks: &mut Chunks, textures: &mut Assets<Texture>) {
+        for chunk_edit in &self.chunk_edits {
+            let chunk = chunks.get_chunk(chunk_edit.chunk_index);
+
+            for cell_edit in chunk_edit.cell_edits.values() {
+                chunk.set_cell(cell_edit.cell_position, cell_edit.before, textures);
+            }
+        }
+    }
+
+    pub fn redo(&self, chunks: &mut Chunks, textures: &mut Assets<Texture>) {
+        for chunk_edit in &self.chunk_edits {
+            let chunk = chunks.get_chunk(chunk_edit.chunk_index);
+
+            for cell_edit in chunk_edit.cell_edits.values() {
+                chunk.set_cell(cell_edit.cell_position, cell_edit.after, textures);
+            }
+        }
+    }
+}
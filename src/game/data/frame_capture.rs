@@ -0,0 +1,33 @@
+﻿use crate::game::constants::FRAME_CAPTURE_GIF_SPEED;
+use image::{Delay, Frame, ImageResult, RgbaImage};
+use image::codecs::gif::{GifEncoder, Repeat};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub fn create_capture_directory(root: &Path) -> io::Result<PathBuf> {
+    let directory = (0..)
+        .map(|capture_index| root.join(format!("capture_{:03}", capture_index)))
+        .find(|directory| !directory.exists())
+        .unwrap();
+
+    fs::create_dir_all(&directory)?;
+
+    Ok(directory)
+}
+
+pub fn write_png_frame(directory: &Path, frame_index: u32, image: &RgbaImage) -> ImageResult<()> {
+    image.save(directory.join(format!("frame_{:05}.png", frame_index)))
+}
+
+pub fn create_gif_encoder(path: &Path) -> ImageResult<GifEncoder<File>> {
+    let mut encoder = GifEncoder::new_with_speed(File::create(path)?, FRAME_CAPTURE_GIF_SPEED);
+    encoder.set_repeat(Repeat::Infinite)?;
+
+    Ok(encoder)
+}
+
+pub fn write_gif_frame(encoder: &mut GifEncoder<File>, image: RgbaImage, frame_duration: Duration) -> ImageResult<()> {
+    encoder.encode_frame(Frame::from_parts(image, 0, 0, Delay::from_saturating_duration(frame_duration)))
+}
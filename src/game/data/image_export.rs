@@ -0,0 +1,108 @@
+﻿use bevy::prelude::*;
+use crate::game::data::pixel_simulation::{Cell, Cells, Particles, WorldCellPosition};
+use crate::game::constants::WORLD_SIZE;
+use image::{GrayImage, Luma, Rgba, RgbaImage};
+
+// Takes the cells of every chunk (see `Chunks::snapshot_cells`) rather than `Chunks`, so worlds can be rendered
+// without any textures, e.g. straight from a world file.
+pub fn render_cell_colors(chunk_cells: &[Cells], particles: Option<&Particles>) -> RgbaImage {
+    let world_size = WORLD_SIZE as u32;
+    let mut image = RgbaImage::new(world_size, world_size);
+
+    for x in 0..world_size {
+        for y in 0..world_size {
+            if let Some(cell) = get_cell_at(chunk_cells, x, y) {
+                image.put_pixel(x, y, Rgba([cell.color.red, cell.color.green, cell.color.blue, cell.color.alpha]));
+            }
+        }
+    }
+
+    if let Some(particles) = particles {
+        for particle in particles.iter() {
+            let particle_position = particle.position.as_i32();
+
+            if WorldCellPosition(particle_position).is_in_world() {
+                image.put_pixel(particle_position.x as u32, particle_position.y as u32, Rgba([particle.color.red, particle.color.green, particle.color.blue, particle.color.alpha]));
+            }
+        }
+    }
+
+    image
+}
+
+pub fn render_material_ids(chunk_cells: &[Cells]) -> GrayImage {
+    let world_size = WORLD_SIZE as u32;
+    let mut image = GrayImage::new(world_size, world_size);
+
+    for x in 0..world_size {
+        for y in 0..world_size {
+            let cell = get_cell_at(chunk_cells, x, y);
+
+            image.put_pixel(x, y, Luma([cell.map_or(0, |cell| cell.cell_type.to_id())]));
+        }
+    }
+
+    image
+}
+
+fn get_cell_at(chunk_cells: &[Cells], x: u32, y: u32) -> Option<Cell> {
+    let (chunk_index, chunk_cell_position) = WorldCellPosition(IVec2::new(x as i32, y as i32)).to_chunk_cell_position()?;
+
+    chunk_cells.get(*chunk_index)?.get_cell(chunk_cell_position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::constants::CHUNK_SIZE;
+    use crate::game::data::pixel_simulation::{CellType, ChunkCellPosition, Particle};
+    use palette::Srgba;
+
+    fn test_chunk_cells() -> Vec<Cells> {
+        let mut chunk_cells = vec![Cells::new(); 9];
+
+        chunk_cells[0].set_cell(ChunkCellPosition(UVec2::new(1, 2)), Some(Cell {
+            cell_type: CellType::Sand,
+            color: Srgba::new(200, 100, 50, 255),
+            last_iteration_updated: 0
+        }));
+        chunk_cells[4].set_cell(ChunkCellPosition(UVec2::new(0, 0)), Some(Cell {
+            cell_type: CellType::Water,
+            color: Srgba::new(10, 20, 250, 180),
+            last_iteration_updated: 0
+        }));
+
+        chunk_cells
+    }
+
+    #[test]
+    fn renders_cell_colors() {
+        let image = render_cell_colors(&test_chunk_cells(), None);
+        let world_size = WORLD_SIZE as u32;
+
+        assert_eq!(image.dimensions(), (world_size, world_size));
+        assert_eq!(*image.get_pixel(1, 2), Rgba([200, 100, 50, 255]));
+        assert_eq!(*image.get_pixel(CHUNK_SIZE as u32, CHUNK_SIZE as u32), Rgba([10, 20, 250, 180]));
+        assert_eq!(*image.get_pixel(0, 0), Rgba([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn renders_particles_only_when_given() {
+        let mut particles = Particles::new();
+        particles.add_particle(Particle::new(CellType::Water, Vec2::new(5.5, 6.5), Vec2::ZERO, Srgba::new(1, 2, 3, 4)));
+
+        let chunk_cells = test_chunk_cells();
+
+        assert_eq!(*render_cell_colors(&chunk_cells, Some(&particles)).get_pixel(5, 6), Rgba([1, 2, 3, 4]));
+        assert_eq!(*render_cell_colors(&chunk_cells, None).get_pixel(5, 6), Rgba([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn renders_material_ids() {
+        let image = render_material_ids(&test_chunk_cells());
+
+        assert_eq!(*image.get_pixel(1, 2), Luma([CellType::Sand.to_id()]));
+        assert_eq!(*image.get_pixel(CHUNK_SIZE as u32, CHUNK_SIZE as u32), Luma([CellType::Water.to_id()]));
+        assert_eq!(*image.get_pixel(0, 0), Luma([0]));
+    }
+}
@@ -0,0 +1,189 @@
+﻿use bevy::prelude::*;
+use crate::game::data::pixel_simulation::{Cell, CellType, Chunks, WorldCellPosition};
+use crate::game::data::edit_command::EditCommand;
+use crate::game::constants::WORLD_SIZE;
+use image::RgbaImage;
+use palette::Srgba;
+use std::fmt;
+
+const TRANSPARENCY_THRESHOLD: u8 = 128;
+
+#[derive(Debug)]
+pub enum ImageImportError {
+    Io(std::io::Error),
+    Image(image::ImageError),
+    InvalidPalette { line: usize },
+    EmptyPalette
+}
+
+impl fmt::Display for ImageImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImageImportError::Io(error) => write!(f, "{}", error),
+            ImageImportError::Image(error) => write!(f, "{}", error),
+            ImageImportError::InvalidPalette { line } => write!(f, "invalid palette entry on line {}", line),
+            ImageImportError::EmptyPalette => write!(f, "palette has no entries")
+        }
+    }
+}
+
+impl std::error::Error for ImageImportError {}
+
+impl From<std::io::Error> for ImageImportError {
+    fn from(error: std::io::Error) -> Self {
+        ImageImportError::Io(error)
+    }
+}
+
+impl From<image::ImageError> for ImageImportError {
+    fn from(error: image::ImageError) -> Self {
+        ImageImportError::Image(error)
+    }
+}
+
+pub struct ColorPalette {
+    entries: Vec<([u8; 3], CellType)>
+}
+
+impl ColorPalette {
+    pub fn parse(source: &str) -> Result<Self, ImageImportError> {
+        let mut entries = Vec::new();
+
+        for (line_index, line) in source.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+
+            let invalid_palette = || ImageImportError::InvalidPalette { line: line_index + 1 };
+            let mut parts = line.split_whitespace();
+
+            let color = parts.next()
+                .and_then(parse_hex_color)
+                .ok_or_else(invalid_palette)?;
+            let cell_type = parts.next()
+                .and_then(CellType::from_name)
+                .ok_or_else(invalid_palette)?;
+
+            entries.push((color, cell_type));
+        }
+
+        if entries.is_empty() {
+            return Err(ImageImportError::EmptyPalette);
+        }
+
+        Ok(Self {
+            entries
+        })
+    }
+
+    pub fn find_cell_type(&self, color: [u8; 3]) -> Option<CellType> {
+        self.entries.iter()
+            .min_by_key(|(entry_color, _)| {
+                entry_color.iter()
+                    .zip(color.iter())
+                    .map(|(a, b)| (*a as i32 - *b as i32).pow(2))
+                    .sum::<i32>()
+            })
+            .map(|(_, cell_type)| *cell_type)
+    }
+}
+
+fn parse_hex_color(hex: &str) -> Option<[u8; 3]> {
+    let hex = hex.strip_prefix('#')?;
+
+    if hex.len() != 6 {
+        return None;
+    }
+
+    let channel = |index: usize| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok();
+
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+pub fn import_image(image: &RgbaImage, palette: &ColorPalette, chunks: &mut Chunks, edit: &mut EditCommand, textures: &mut Assets<Texture>) {
+    let world_size = WORLD_SIZE as u32;
+
+    for x in 0..world_size {
+        for y in 0..world_size {
+            edit.set_cell_at(chunks, WorldCellPosition(IVec2::new(x as i32, y as i32)), image_cell_at(image, palette, x, y), textures);
+        }
+    }
+}
+
+fn image_cell_at(image: &RgbaImage, palette: &ColorPalette, x: u32, y: u32) -> Option<Cell> {
+    if x >= image.width() || y >= image.height() {
+        return None;
+    }
+
+    let [red, green, blue, alpha] = image.get_pixel(x, y).0;
+
+    if alpha < TRANSPARENCY_THRESHOLD {
+        return None;
+    }
+
+    palette.find_cell_type([red, green, blue]).map(|cell_type| Cell {
+        cell_type,
+        color: Srgba::new(red, green, blue, 255),
+        last_iteration_updated: 0
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    const TEST_PALETTE: &str = "// test palette\n#c2b280 sand\n\n#1e64c8 water\n";
+
+    fn cell_summary(cell: Option<Cell>) -> Option<(u8, Srgba<u8>)> {
+        cell.map(|cell| (cell.cell_type.to_id(), cell.color))
+    }
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(parse_hex_color("#c2B280"), Some([0xc2, 0xb2, 0x80]));
+        assert_eq!(parse_hex_color("c2b280"), None);
+        assert_eq!(parse_hex_color("#c2b28"), None);
+        assert_eq!(parse_hex_color("#c2b2zz"), None);
+    }
+
+    #[test]
+    fn parses_palettes() {
+        let palette = ColorPalette::parse(TEST_PALETTE).unwrap();
+
+        assert_eq!(palette.entries.len(), 2);
+        assert_eq!(palette.entries[0].0, [0xc2, 0xb2, 0x80]);
+        assert_eq!(palette.entries[1].1.to_id(), CellType::Water.to_id());
+    }
+
+    #[test]
+    fn rejects_invalid_palettes() {
+        assert!(matches!(ColorPalette::parse("#c2b280 sand\n#1e64c8 lava\n"), Err(ImageImportError::InvalidPalette { line: 2 })));
+        assert!(matches!(ColorPalette::parse("sand #c2b280\n"), Err(ImageImportError::InvalidPalette { line: 1 })));
+        assert!(matches!(ColorPalette::parse("// only a comment\n\n"), Err(ImageImportError::EmptyPalette)));
+    }
+
+    #[test]
+    fn finds_the_nearest_cell_type() {
+        let palette = ColorPalette::parse(TEST_PALETTE).unwrap();
+
+        assert_eq!(palette.find_cell_type([0xc0, 0xb0, 0x70]).map(|cell_type| cell_type.to_id()), Some(CellType::Sand.to_id()));
+        assert_eq!(palette.find_cell_type([0, 80, 255]).map(|cell_type| cell_type.to_id()), Some(CellType::Water.to_id()));
+    }
+
+    #[test]
+    fn skips_transparent_and_out_of_image_pixels() {
+        let palette = ColorPalette::parse(TEST_PALETTE).unwrap();
+        let mut image = RgbaImage::new(2, 1);
+
+        image.put_pixel(0, 0, Rgba([0xc2, 0xb2, 0x80, TRANSPARENCY_THRESHOLD]));
+        image.put_pixel(1, 0, Rgba([0xc2, 0xb2, 0x80, TRANSPARENCY_THRESHOLD - 1]));
+
+        assert_eq!(cell_summary(image_cell_at(&image, &palette, 0, 0)), Some((CellType::Sand.to_id(), Srgba::new(0xc2, 0xb2, 0x80, 255))));
+        assert_eq!(cell_summary(image_cell_at(&image, &palette, 1, 0)), None);
+        assert_eq!(cell_summary(image_cell_at(&image, &palette, 2, 0)), None);
+        assert_eq!(cell_summary(image_cell_at(&image, &palette, 0, 1)), None);
+    }
+}
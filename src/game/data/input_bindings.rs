@@ -0,0 +1,324 @@
+﻿use bevy::prelude::*;
+use std::collections::HashSet;
+use std::fmt;
+
+const DEFAULT_INPUT_BINDINGS: &str = include_str!("../../../assets/config/input_bindings.cfg");
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum InputAction {
+    Place,
+    PlaceSecondary,
+    Erase,
+    Pan,
+    PanLeft,
+    PanRight,
+    PanUp,
+    PanDown,
+    PickMaterial,
+    NextMaterial,
+    Zoom,
+    ResizeBrush,
+    Pause,
+    Step,
+    StepMany,
+    SpeedUp,
+    SlowDown,
+    ResetSpeed,
+    Undo,
+    Redo,
+    SaveWorld,
+    LoadWorld,
+    ExportImage,
+    ExportImageWithParticles,
+    ToggleRecording,
+    ReplayRecording,
+    ToggleFrameCapture,
+    ToggleGifCapture,
+    CycleBrushShape,
+    CycleBrushMode,
+    ToggleEraser,
+    FillTool,
+    LineTool,
+    RectangleTool,
+    CircleTool,
+    ToggleDebugOverlay
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 36] = [
+        InputAction::Place,
+        InputAction::PlaceSecondary,
+        InputAction::Erase,
+        InputAction::Pan,
+        InputAction::PanLeft,
+        InputAction::PanRight,
+        InputAction::PanUp,
+        InputAction::PanDown,
+        InputAction::PickMaterial,
+        InputAction::NextMaterial,
+        InputAction::Zoom,
+        InputAction::ResizeBrush,
+        InputAction::Pause,
+        InputAction::Step,
+        InputAction::StepMany,
+        InputAction::SpeedUp,
+        InputAction::SlowDown,
+        InputAction::ResetSpeed,
+        InputAction::Undo,
+        InputAction::Redo,
+        InputAction::SaveWorld,
+        InputAction::LoadWorld,
+        InputAction::ExportImage,
+        InputAction::ExportImageWithParticles,
+        InputAction::ToggleRecording,
+        InputAction::ReplayRecording,
+        InputAction::ToggleFrameCapture,
+        InputAction::ToggleGifCapture,
+        InputAction::CycleBrushShape,
+        InputAction::CycleBrushMode,
+        InputAction::ToggleEraser,
+        InputAction::FillTool,
+        InputAction::LineTool,
+        InputAction::RectangleTool,
+        InputAction::CircleTool,
+        InputAction::ToggleDebugOverlay
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            InputAction::Place => "place",
+            InputAction::PlaceSecondary => "place_secondary",
+            InputAction::Erase => "erase",
+            InputAction::Pan => "pan",
+            InputAction::PanLeft => "pan_left",
+            InputAction::PanRight => "pan_right",
+            InputAction::PanUp => "pan_up",
+            InputAction::PanDown => "pan_down",
+            InputAction::PickMaterial => "pick_material",
+            InputAction::NextMaterial => "next_material",
+            InputAction::Zoom => "zoom",
+            InputAction::ResizeBrush => "resize_brush",
+            InputAction::Pause => "pause",
+            InputAction::Step => "step",
+            InputAction::StepMany => "step_many",
+            InputAction::SpeedUp => "speed_up",
+            InputAction::SlowDown => "slow_down",
+            InputAction::ResetSpeed => "reset_speed",
+            InputAction::Undo => "undo",
+            InputAction::Redo => "redo",
+            InputAction::SaveWorld => "save_world",
+            InputAction::LoadWorld => "load_world",
+            InputAction::ExportImage => "export_image",
+            InputAction::ExportImageWithParticles => "export_image_with_particles",
+            InputAction::ToggleRecording => "toggle_recording",
+            InputAction::ReplayRecording => "replay_recording",
+            InputAction::ToggleFrameCapture => "toggle_frame_capture",
+            InputAction::ToggleGifCapture => "toggle_gif_capture",
+            InputAction::CycleBrushShape => "cycle_brush_shape",
+            InputAction::CycleBrushMode => "cycle_brush_mode",
+            InputAction::ToggleEraser => "toggle_eraser",
+            InputAction::FillTool => "fill_tool",
+            InputAction::LineTool => "line_tool",
+            InputAction::RectangleTool => "rectangle_tool",
+            InputAction::CircleTool => "circle_tool",
+            InputAction::ToggleDebugOverlay => "toggle_debug_overlay"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        InputAction::ALL.iter().copied().find(|action| action.name() == name)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum InputSource {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    MouseWheel
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InputBinding {
+    pub source: InputSource,
+    pub control: bool,
+    pub shift: bool
+}
+
+impl InputBinding {
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts: Vec<&str> = text.split('+').collect();
+        let source = parse_input_source(parts.pop()?)?;
+        let mut control = false;
+        let mut shift = false;
+
+        for modifier in parts {
+            match modifier {
+                "ctrl" => control = true,
+                "shift" => shift = true,
+                _ => return None
+            }
+        }
+
+        Some(Self {
+            source,
+            control,
+            shift
+        })
+    }
+
+    pub fn are_modifiers_held(&self, is_control_pressed: bool, is_shift_pressed: bool) -> bool {
+        (!self.control || is_control_pressed) && (!self.shift || is_shift_pressed)
+    }
+
+    pub fn modifier_count(&self) -> u32 {
+        self.control as u32 + self.shift as u32
+    }
+}
+
+#[derive(Debug)]
+pub enum InputBindingsError {
+    Io(std::io::Error),
+    InvalidBinding { line: usize }
+}
+
+impl fmt::Display for InputBindingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputBindingsError::Io(error) => write!(f, "{}", error),
+            InputBindingsError::InvalidBinding { line } => write!(f, "invalid input binding on line {}", line)
+        }
+    }
+}
+
+impl std::error::Error for InputBindingsError {}
+
+impl From<std::io::Error> for InputBindingsError {
+    fn from(error: std::io::Error) -> Self {
+        InputBindingsError::Io(error)
+    }
+}
+
+pub struct InputBindings {
+    bindings: Vec<(InputAction, InputBinding)>,
+    configured_actions: HashSet<InputAction>
+}
+
+impl InputBindings {
+    pub fn parse(source: &str) -> Result<Self, InputBindingsError> {
+        let mut bindings = Vec::new();
+        let mut configured_actions = HashSet::new();
+
+        for (line_index, line) in source.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+
+            let invalid_binding = || InputBindingsError::InvalidBinding { line: line_index + 1 };
+            let mut parts = line.split_whitespace();
+
+            let action = parts.next()
+                .and_then(InputAction::from_name)
+                .ok_or_else(invalid_binding)?;
+
+            configured_actions.insert(action);
+
+            for part in parts {
+                bindings.push((action, InputBinding::parse(part).ok_or_else(invalid_binding)?));
+            }
+        }
+
+        Ok(Self {
+            bindings,
+            configured_actions
+        })
+    }
+
+    // Actions these bindings don't mention keep the bindings they have in `base`.
+    pub fn layered_over(mut self, base: InputBindings) -> Self {
+        let base_bindings = base.bindings.into_iter()
+            .filter(|(action, _)| !self.configured_actions.contains(action));
+
+        self.bindings.extend(base_bindings);
+        self.configured_actions.extend(base.configured_actions);
+        self
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(InputAction, InputBinding)> {
+        self.bindings.iter()
+    }
+
+    // When several bindings of a source have their modifiers held, only the ones with the most modifiers count,
+    // so e.g. ctrl+z undoes without also triggering a plain z binding.
+    pub fn resolve(&self, source: InputSource, is_control_pressed: bool, is_shift_pressed: bool) -> Vec<InputAction> {
+        let matching_bindings = self.bindings.iter()
+            .filter(|(_, binding)| binding.source == source && binding.are_modifiers_held(is_control_pressed, is_shift_pressed));
+
+        let max_modifier_count = matching_bindings.clone()
+            .map(|(_, binding)| binding.modifier_count())
+            .max();
+
+        matching_bindings
+            .filter(|(_, binding)| Some(binding.modifier_count()) == max_modifier_count)
+            .map(|(action, _)| *action)
+            .collect()
+    }
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self::parse(DEFAULT_INPUT_BINDINGS).unwrap()
+    }
+}
+
+fn parse_input_source(name: &str) -> Option<InputSource> {
+    let mouse_button = match name {
+        "mouse_left" => Some(MouseButton::Left),
+        "mouse_right" => Some(MouseButton::Right),
+        "mouse_middle" => Some(MouseButton::Middle),
+        _ => None
+    };
+
+    if name == "mouse_wheel" {
+        return Some(InputSource::MouseWheel);
+    }
+
+    mouse_button.map(InputSource::Mouse).or_else(|| parse_key_code(name).map(InputSource::Key))
+}
+
+fn parse_key_code(name: &str) -> Option<KeyCode> {
+    let key_code = match name {
+        "a" => KeyCode::A, "b" => KeyCode::B, "c" => KeyCode::C, "d" => KeyCode::D,
+        "e" => KeyCode::E, "f" => KeyCode::F, "g" => KeyCode::G, "h" => KeyCode::H,
+        "i" => KeyCode::I, "j" => KeyCode::J, "k" => KeyCode::K, "l" => KeyCode::L,
+        "m" => KeyCode::M, "n" => KeyCode::N, "o" => KeyCode::O, "p" => KeyCode::P,
+        "q" => KeyCode::Q, "r" => KeyCode::R, "s" => KeyCode::S, "t" => KeyCode::T,
+        "u" => KeyCode::U, "v" => KeyCode::V, "w" => KeyCode::W, "x" => KeyCode::X,
+        "y" => KeyCode::Y, "z" => KeyCode::Z,
+        "key0" => KeyCode::Key0, "key1" => KeyCode::Key1, "key2" => KeyCode::Key2, "key3" => KeyCode::Key3,
+        "key4" => KeyCode::Key4, "key5" => KeyCode::Key5, "key6" => KeyCode::Key6, "key7" => KeyCode::Key7,
+        "key8" => KeyCode::Key8, "key9" => KeyCode::Key9,
+        "f1" => KeyCode::F1, "f2" => KeyCode::F2, "f3" => KeyCode::F3, "f4" => KeyCode::F4,
+        "f5" => KeyCode::F5, "f6" => KeyCode::F6, "f7" => KeyCode::F7, "f8" => KeyCode::F8,
+        "f9" => KeyCode::F9, "f10" => KeyCode::F10, "f11" => KeyCode::F11, "f12" => KeyCode::F12,
+        "left" => KeyCode::Left, "right" => KeyCode::Right, "up" => KeyCode::Up, "down" => KeyCode::Down,
+        "space" => KeyCode::Space, "tab" => KeyCode::Tab, "escape" => KeyCode::Escape,
+        "return" => KeyCode::Return, "back" => KeyCode::Back, "delete" => KeyCode::Delete,
+        "insert" => KeyCode::Insert, "home" => KeyCode::Home, "end" => KeyCode::End,
+        "page_up" => KeyCode::PageUp, "page_down" => KeyCode::PageDown,
+        "period" => KeyCode::Period, "comma" => KeyCode::Comma, "slash" => KeyCode::Slash,
+        "semicolon" => KeyCode::Semicolon, "apostrophe" => KeyCode::Apostrophe, "grave" => KeyCode::Grave,
+        "lbracket" => KeyCode::LBracket, "rbracket" => KeyCode::RBracket, "backslash" => KeyCode::Backslash,
+        "equals" => KeyCode::Equals, "minus" => KeyCode::Minus,
+        "numpad_add" => KeyCode::NumpadAdd, "numpad_subtract" => KeyCode::NumpadSubtract,
+        "numpad_multiply" => KeyCode::NumpadMultiply, "numpad_divide" => KeyCode::NumpadDivide,
+        "numpad0" => KeyCode::Numpad0, "numpad1" => KeyCode::Numpad1, "numpad2" => KeyCode::Numpad2,
+        "numpad3" => KeyCode::Numpad3, "numpad4" => KeyCode::Numpad4, "numpad5" => KeyCode::Numpad5,
+        "numpad6" => KeyCode::Numpad6, "numpad7" => KeyCode::Numpad7, "numpad8" => KeyCode::Numpad8,
+        "numpad9" => KeyCode::Numpad9,
+        _ => return None
+    };
+
+    Some(key_code)
+}
\ No newline at end of file
@@ -1,3 +1,17 @@
-﻿pub mod chunk_changes;
+﻿pub mod action_recording;
+pub mod brush;
+pub mod cell_colors;
+pub mod cell_traversal;
+pub mod chunk_changes;
+pub mod coordinates;
+pub mod edit_command;
+pub mod frame_capture;
+pub mod image_export;
+pub mod image_import;
+pub mod input_bindings;
 pub mod pixel_simulation;
-pub mod system_labels;
\ No newline at end of file
+pub mod shape_drawing;
+pub mod simulation_action;
+pub mod system_labels;
+pub mod wind_field;
+pub mod world_file;
\ No newline at end of file
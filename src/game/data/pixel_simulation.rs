@@ -1,9 +1,24 @@
 ﻿use bevy::prelude::*;
-use crate::game::constants::CHUNK_SIZE;
+use crate::game::constants::{CHUNK_SIZE, WORLD_SIZE};
 use shrinkwraprs::Shrinkwrap;
 use bevy::render::texture::{Extent3d, TextureDimension, TextureFormat};
 use retain_mut::RetainMut;
 use palette::{Srgba};
+use lazy_static::lazy_static;
+use crate::game::constants::PARTICLE_DEPOSITION_RADIUS;
+
+lazy_static! {
+    static ref DEPOSITION_OFFSETS: Vec<IVec2> = {
+        let radius = PARTICLE_DEPOSITION_RADIUS;
+        let mut offsets: Vec<IVec2> = (-radius..=radius)
+            .flat_map(|x| (-radius..=radius).map(move |y| IVec2::new(x, y)))
+            .filter(|offset| offset.x * offset.x + offset.y * offset.y <= radius * radius)
+            .collect();
+
+        offsets.sort_by_key(|offset| (offset.x * offset.x + offset.y * offset.y, offset.y));
+        offsets
+    };
+}
 
 #[derive(Shrinkwrap, Clone, Copy)]
 pub struct WorldCellPosition(pub IVec2);
@@ -11,6 +26,23 @@ pub struct WorldCellPosition(pub IVec2);
 #[derive(Shrinkwrap, Clone, Copy)]
 pub struct ChunkCellPosition(pub UVec2);
 
+impl WorldCellPosition {
+    pub fn is_in_world(&self) -> bool {
+        self.x >= 0 && self.x < WORLD_SIZE as i32 && self.y >= 0 && self.y < WORLD_SIZE as i32
+    }
+
+    pub fn to_chunk_cell_position(&self) -> Option<(ChunkIndex, ChunkCellPosition)> {
+        if !self.is_in_world() {
+            return None;
+        }
+
+        let chunk_position = ChunkPosition(self.as_u32() / (CHUNK_SIZE as u32));
+        let chunk_cell_position = ChunkCellPosition(self.as_u32() - (*chunk_position * (CHUNK_SIZE as u32)));
+
+        Some((ChunkIndex::from_chunk_position(chunk_position), chunk_cell_position))
+    }
+}
+
 #[derive(Shrinkwrap, Clone, Copy)]
 pub struct ChunkIndex(pub usize);
 
@@ -64,9 +96,80 @@ impl Chunks {
         }
     }
     
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn count_cells_of_type(&self, cell_type: CellType) -> usize {
+        self.chunks.iter().map(|chunk| chunk.get_cells().count_cells_of_type(cell_type)).sum()
+    }
+
     pub fn get_chunk(&mut self, chunk_index: ChunkIndex) -> &mut Chunk {
         &mut self.chunks[*chunk_index]
     }
+
+    pub fn snapshot_cells(&self) -> Vec<Cells> {
+        self.chunks.iter().map(|chunk| chunk.get_cells().clone()).collect()
+    }
+
+    pub fn get_cell_at(&self, world_cell_position: WorldCellPosition) -> Option<Cell> {
+        let (chunk_index, chunk_cell_position) = world_cell_position.to_chunk_cell_position()?;
+
+        self.chunks[*chunk_index].get_cell(chunk_cell_position)
+    }
+
+    pub fn find_free_cell_near(&self, world_cell_position: WorldCellPosition) -> Option<WorldCellPosition> {
+        DEPOSITION_OFFSETS.iter()
+            .map(|offset| WorldCellPosition(*world_cell_position + *offset))
+            .find(|cell_position| cell_position.is_in_world() && self.get_cell_at(*cell_position).is_none())
+    }
+
+    pub fn set_cell_at(&mut self, world_cell_position: WorldCellPosition, cell: Option<Cell>, textures: &mut Assets<Texture>) {
+        if let Some((chunk_index, chunk_cell_position)) = world_cell_position.to_chunk_cell_position() {
+            self.chunks[*chunk_index].set_cell(chunk_cell_position, cell, textures);
+        }
+    }
+
+    pub fn set_overlay_color_at(&mut self, world_cell_position: WorldCellPosition, color: Srgba<u8>, textures: &mut Assets<Texture>) {
+        if let Some((chunk_index, chunk_cell_position)) = world_cell_position.to_chunk_cell_position() {
+            self.chunks[*chunk_index].overlay_texture.set_color(chunk_cell_position, color, textures);
+        }
+    }
+
+    pub fn find_connected_region(&self, start: WorldCellPosition) -> Vec<WorldCellPosition> {
+        if !start.is_in_world() {
+            return Vec::new();
+        }
+
+        let world_size = WORLD_SIZE as i32;
+        let region_cell_type = self.get_cell_at(start).map(|cell| cell.cell_type);
+        let mut visited = vec![false; (world_size * world_size) as usize];
+        let mut pending_positions = vec![start];
+        let mut region = Vec::new();
+
+        visited[(start.y * world_size + start.x) as usize] = true;
+
+        while let Some(position) = pending_positions.pop() {
+            region.push(position);
+
+            for offset in [IVec2::new(1, 0), IVec2::new(-1, 0), IVec2::new(0, 1), IVec2::new(0, -1)].iter() {
+                let neighbour = WorldCellPosition(*position + *offset);
+
+                if !neighbour.is_in_world() {
+                    continue;
+                }
+
+                let visited_index = (neighbour.y * world_size + neighbour.x) as usize;
+
+                if !visited[visited_index] && self.get_cell_at(neighbour).map(|cell| cell.cell_type) == region_cell_type {
+                    visited[visited_index] = true;
+                    pending_positions.push(neighbour);
+                }
+            }
+        }
+
+        region
+    }
 }
 
 #[derive(Clone)]
@@ -138,14 +241,16 @@ impl ChunkTexture {
 pub struct Chunk {
     pub main_texture: ChunkTexture,
     pub particles_texture: ChunkTexture,
+    pub overlay_texture: ChunkTexture,
     cells: Cells
 }
 
 impl Chunk {
-    pub fn new(main_texture: ChunkTexture, particles_texture: ChunkTexture) -> Self {
+    pub fn new(main_texture: ChunkTexture, particles_texture: ChunkTexture, overlay_texture: ChunkTexture) -> Self {
         Self {
             main_texture,
             particles_texture,
+            overlay_texture,
             cells: Cells::new()
         }
     }
@@ -162,6 +267,10 @@ impl Chunk {
         self.particles_texture.clear(textures);
     }
 
+    pub fn get_overlay_texture(&self) -> &ChunkTexture {
+        &self.overlay_texture
+    }
+
     pub fn get_cell(&self, cell_position: ChunkCellPosition) -> Option<Cell> {
         self.cells.get_cell(cell_position)
     }
@@ -170,6 +279,20 @@ impl Chunk {
         self.cells.set_cell(cell_position, cell);
         self.main_texture.set_color(cell_position, cell.map(|cell| cell.color).unwrap_or(Srgba::<u8>::new(0, 0, 0, 0)), textures);
     }
+
+    pub fn get_cells(&self) -> &Cells {
+        &self.cells
+    }
+
+    pub fn set_cells(&mut self, cells: &Cells, textures: &mut Assets<Texture>) {
+        for x in 0..CHUNK_SIZE {
+            for y in 0..CHUNK_SIZE {
+                let cell_position = ChunkCellPosition(UVec2::new(x as u32, y as u32));
+
+                self.set_cell(cell_position, cells.get_cell(cell_position), textures);
+            }
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -188,6 +311,26 @@ impl Particles {
         self.particles.push(particle);
     }
 
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        self.particles.truncate(len);
+    }
+
+    pub fn clear(&mut self) {
+        self.particles.clear();
+    }
+
+    pub fn drain_oldest(&mut self, count: usize) -> std::vec::Drain<Particle> {
+        self.particles.drain(..count.min(self.particles.len()))
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<Particle> {
+        self.particles.iter()
+    }
+
     pub fn retain_mut<F>(&mut self, f: F) where F: FnMut(&mut Particle) -> bool {
         self.particles.retain_mut(f);
     }
@@ -207,7 +350,37 @@ pub struct Particle {
     pub particle_type: CellType,
     pub position: Vec2,
     pub velocity: Vec2,
-    pub color: Srgba<u8>
+    pub color: Srgba<u8>,
+    pub drag: f32,
+    pub restitution: f32,
+    pub friction: f32
+}
+
+impl Particle {
+    pub fn new(particle_type: CellType, position: Vec2, velocity: Vec2, color: Srgba<u8>) -> Self {
+        let (drag, restitution, friction) = match particle_type {
+            CellType::Sand => (0.5, 0.2, 0.6),
+            CellType::Water => (0.2, 0.4, 0.05)
+        };
+
+        Self {
+            particle_type,
+            position,
+            velocity,
+            color,
+            drag,
+            restitution,
+            friction
+        }
+    }
+
+    pub fn to_cell(&self) -> Cell {
+        Cell {
+            cell_type: self.particle_type,
+            color: self.color,
+            last_iteration_updated: 0
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -231,6 +404,12 @@ impl Cells {
     pub fn set_cell(&mut self, cell_position: ChunkCellPosition, cell: Option<Cell>) {
         self.cells[cell_position.x as usize][cell_position.y as usize] = cell;
     }
+
+    pub fn count_cells_of_type(&self, cell_type: CellType) -> usize {
+        self.cells.iter().flatten()
+            .filter(|cell| cell.map_or(false, |cell| cell.cell_type == cell_type))
+            .count()
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -240,8 +419,38 @@ pub struct Cell {
     pub last_iteration_updated: u64
 }
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub enum CellType {
     Sand,
     Water
 }
+
+impl CellType {
+    pub const ALL: [CellType; 2] = [CellType::Sand, CellType::Water];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CellType::Sand => "sand",
+            CellType::Water => "water"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        CellType::ALL.iter().copied().find(|cell_type| cell_type.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn to_id(self) -> u8 {
+        match self {
+            CellType::Sand => 1,
+            CellType::Water => 2
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(CellType::Sand),
+            2 => Some(CellType::Water),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,161 @@
+﻿use bevy::prelude::*;
+use crate::game::constants::WORLD_SIZE;
+use crate::game::data::brush::BrushShape;
+use crate::game::data::pixel_simulation::WorldCellPosition;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DrawShape {
+    Line,
+    Rectangle,
+    Circle
+}
+
+impl DrawShape {
+    pub fn name(self) -> &'static str {
+        match self {
+            DrawShape::Line => "line",
+            DrawShape::Rectangle => "rectangle",
+            DrawShape::Circle => "circle"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "line" => Some(DrawShape::Line),
+            "rectangle" => Some(DrawShape::Rectangle),
+            "circle" => Some(DrawShape::Circle),
+            _ => None
+        }
+    }
+
+    // Only cells inside the world are generated, so dragging far outside of it while zoomed out stays cheap.
+    pub fn cell_positions(self, from: IVec2, to: IVec2, thickness: u32, is_filled: bool) -> Vec<IVec2> {
+        let mut cell_positions = match self {
+            DrawShape::Line => line_cell_positions(from, to, thickness),
+            DrawShape::Rectangle => rectangle_cell_positions(from, to, thickness, is_filled),
+            DrawShape::Circle => circle_cell_positions(from, to, thickness, is_filled)
+        };
+
+        cell_positions.sort_by_key(|cell_position| (cell_position.y, cell_position.x));
+        cell_positions.dedup();
+        cell_positions
+    }
+}
+
+fn bresenham_line(from: IVec2, to: IVec2) -> Vec<IVec2> {
+    let delta = (to - from).abs();
+    let step = (to - from).signum();
+    let mut error = delta.x - delta.y;
+    let mut current = from;
+    let mut cell_positions = vec![current];
+
+    while current != to {
+        let doubled_error = error * 2;
+
+        if doubled_error > -delta.y {
+            error -= delta.y;
+            current.x += step.x;
+        }
+
+        if doubled_error < delta.x {
+            error += delta.x;
+            current.y += step.y;
+        }
+
+        cell_positions.push(current);
+    }
+
+    cell_positions
+}
+
+// Clips the segment to the given bounds (Liang-Barsky), so walking it never leaves them.
+fn clip_line(from: IVec2, to: IVec2, min: IVec2, max: IVec2) -> Option<(IVec2, IVec2)> {
+    let start = from.as_f32();
+    let delta = (to - from).as_f32();
+    let mut t_min = 0f32;
+    let mut t_max = 1f32;
+
+    let edges = [
+        (-delta.x, start.x - min.x as f32),
+        (delta.x, max.x as f32 - start.x),
+        (-delta.y, start.y - min.y as f32),
+        (delta.y, max.y as f32 - start.y)
+    ];
+
+    for &(direction, distance) in edges.iter() {
+        if direction == 0. {
+            if distance < 0. {
+                return None;
+            }
+        } else if direction < 0. {
+            t_min = t_min.max(distance / direction);
+        } else {
+            t_max = t_max.min(distance / direction);
+        }
+    }
+
+    if t_min > t_max {
+        return None;
+    }
+
+    Some(((start + delta * t_min).round().as_i32(), (start + delta * t_max).round().as_i32()))
+}
+
+fn line_cell_positions(from: IVec2, to: IVec2, thickness: u32) -> Vec<IVec2> {
+    let offsets: Vec<IVec2> = BrushShape::Circle.offsets(thickness).collect();
+
+    let reach = thickness as i32;
+    let bounds_min = IVec2::splat(-reach);
+    let bounds_max = IVec2::splat(WORLD_SIZE as i32 - 1 + reach);
+
+    let (from, to) = match clip_line(from, to, bounds_min, bounds_max) {
+        Some(clipped_line) => clipped_line,
+        None => return Vec::new()
+    };
+
+    bresenham_line(from, to).into_iter()
+        .flat_map(|cell_position| offsets.iter().map(move |offset| cell_position + *offset))
+        .filter(|cell_position| WorldCellPosition(*cell_position).is_in_world())
+        .collect()
+}
+
+fn rectangle_cell_positions(from: IVec2, to: IVec2, thickness: u32, is_filled: bool) -> Vec<IVec2> {
+    let min = from.min(to);
+    let max = from.max(to);
+    let thickness = thickness as i32;
+    let world_max = WORLD_SIZE as i32 - 1;
+    let (min_x, max_x) = (min.x.max(0), max.x.min(world_max));
+    let (min_y, max_y) = (min.y.max(0), max.y.min(world_max));
+
+    (min_x..=max_x)
+        .flat_map(|x| (min_y..=max_y).map(move |y| IVec2::new(x, y)))
+        .filter(|cell_position| {
+            is_filled
+                || cell_position.x - min.x <= thickness
+                || max.x - cell_position.x <= thickness
+                || cell_position.y - min.y <= thickness
+                || max.y - cell_position.y <= thickness
+        })
+        .collect()
+}
+
+fn circle_cell_positions(center: IVec2, edge: IVec2, thickness: u32, is_filled: bool) -> Vec<IVec2> {
+    let radius = (edge - center).as_f32().length().round() as i64;
+    let inner_radius = radius - thickness as i64 - 1;
+    let world_max = WORLD_SIZE as i64 - 1;
+    let (min_x, max_x) = ((center.x as i64 - radius).max(0), (center.x as i64 + radius).min(world_max));
+    let (min_y, max_y) = ((center.y as i64 - radius).max(0), (center.y as i64 + radius).min(world_max));
+
+    (min_x..=max_x)
+        .flat_map(|x| (min_y..=max_y).map(move |y| (x, y)))
+        .filter(|(x, y)| {
+            let (offset_x, offset_y) = (x - center.x as i64, y - center.y as i64);
+            let distance_squared = offset_x * offset_x + offset_y * offset_y;
+            let is_inside = distance_squared <= radius * (radius + 1);
+            let is_inside_hole = !is_filled && inner_radius >= 0 && distance_squared <= inner_radius * (inner_radius + 1);
+
+            is_inside && !is_inside_hole
+        })
+        .map(|(x, y)| IVec2::new(x as i32, y as i32))
+        .collect()
+}
\ No newline at end of file
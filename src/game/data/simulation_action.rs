@@ -0,0 +1,227 @@
+﻿use bevy::prelude::*;
+use crate::game::components::PixelSimulation;
+use crate::game::data::pixel_simulation::{Cell, CellType, Particle, WorldCellPosition};
+use crate::game::data::cell_colors::random_cell_color;
+use crate::game::data::brush::BrushShape;
+use crate::game::data::shape_drawing::DrawShape;
+use crate::game::data::edit_command::EditCommand;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::ops::Range;
+
+const SPAWN_VELOCITY_SPREAD: Range<f32> = -10. .. 10.;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SimulationAction {
+    SpawnParticles {
+        cell_type: CellType,
+        position: Vec2,
+        count: u32,
+        shape: BrushShape,
+        radius: u32
+    },
+    PaintCells {
+        cell_type: CellType,
+        position: Vec2,
+        shape: BrushShape,
+        radius: u32
+    },
+    Erase {
+        position: Vec2,
+        shape: BrushShape,
+        radius: u32
+    },
+    FloodFill {
+        cell_type: Option<CellType>,
+        position: Vec2
+    },
+    DrawShape {
+        cell_type: Option<CellType>,
+        shape: DrawShape,
+        from: Vec2,
+        to: Vec2,
+        thickness: u32,
+        is_filled: bool
+    },
+    SetPhysics {
+        gravity: f32,
+        wind_particle_coupling: f32
+    }
+}
+
+impl SimulationAction {
+    pub fn apply<R: Rng>(&self, pixel_simulation: &mut PixelSimulation, rng: &mut R, edit: &mut EditCommand, textures: &mut Assets<Texture>) {
+        match *self {
+            SimulationAction::SpawnParticles { cell_type, position, count, shape, radius } => {
+                let offsets: Vec<IVec2> = shape.offsets(radius).collect();
+
+                for _ in 0..count {
+                    let offset = offsets.choose(rng).copied().unwrap_or(IVec2::ZERO);
+                    let color = random_cell_color(cell_type, rng);
+
+                    pixel_simulation.particles.add_particle(Particle::new(
+                        cell_type,
+                        position + offset.as_f32(),
+                        Vec2::new(rng.gen_range(SPAWN_VELOCITY_SPREAD), rng.gen_range(SPAWN_VELOCITY_SPREAD)),
+                        color
+                    ));
+                }
+            }
+            SimulationAction::PaintCells { cell_type, position, shape, radius } => {
+                let center = position.floor().as_i32();
+
+                for offset in shape.offsets(radius) {
+                    let world_cell_position = WorldCellPosition(center + offset);
+
+                    let is_same_cell_type = pixel_simulation.chunks.get_cell_at(world_cell_position)
+                        .map_or(false, |cell| cell.cell_type == cell_type);
+
+                    if world_cell_position.is_in_world() && !is_same_cell_type {
+                        let cell = Cell {
+                            cell_type,
+                            color: random_cell_color(cell_type, rng),
+                            last_iteration_updated: 0
+                        };
+
+                        edit.set_cell_at(&mut pixel_simulation.chunks, world_cell_position, Some(cell), textures);
+                    }
+                }
+            }
+            SimulationAction::Erase { position, shape, radius } => {
+                let center = position.floor().as_i32();
+
+                for offset in shape.offsets(radius) {
+                    let world_cell_position = WorldCellPosition(center + offset);
+
+                    if world_cell_position.is_in_world() {
+                        edit.set_cell_at(&mut pixel_simulation.chunks, world_cell_position, None, textures);
+                    }
+                }
+
+                pixel_simulation.particles.retain_mut(|particle| !shape.contains(particle.position.floor().as_i32() - center, radius));
+            }
+            SimulationAction::FloodFill { cell_type, position } => {
+                let start = WorldCellPosition(position.floor().as_i32());
+                let region_cell_type = pixel_simulation.chunks.get_cell_at(start).map(|cell| cell.cell_type);
+
+                if region_cell_type == cell_type {
+                    return;
+                }
+
+                for world_cell_position in pixel_simulation.chunks.find_connected_region(start) {
+                    let cell = cell_type.map(|cell_type| Cell {
+                        cell_type,
+                        color: random_cell_color(cell_type, rng),
+                        last_iteration_updated: 0
+                    });
+
+                    edit.set_cell_at(&mut pixel_simulation.chunks, world_cell_position, cell, textures);
+                }
+            }
+            SimulationAction::DrawShape { cell_type, shape, from, to, thickness, is_filled } => {
+                for cell_position in shape.cell_positions(from.floor().as_i32(), to.floor().as_i32(), thickness, is_filled) {
+                    let world_cell_position = WorldCellPosition(cell_position);
+
+                    if world_cell_position.is_in_world() {
+                        let cell = cell_type.map(|cell_type| Cell {
+                            cell_type,
+                            color: random_cell_color(cell_type, rng),
+                            last_iteration_updated: 0
+                        });
+
+                        edit.set_cell_at(&mut pixel_simulation.chunks, world_cell_position, cell, textures);
+                    }
+                }
+            }
+            SimulationAction::SetPhysics { gravity, wind_particle_coupling } => {
+                pixel_simulation.gravity = gravity;
+                pixel_simulation.wind_particle_coupling = wind_particle_coupling;
+            }
+        }
+    }
+
+    pub fn to_line(&self) -> String {
+        match self {
+            SimulationAction::SpawnParticles { cell_type, position, count, shape, radius } => {
+                format!("spawn_particles {} {} {} {} {} {}", cell_type.name(), position.x, position.y, count, shape.name(), radius)
+            }
+            SimulationAction::PaintCells { cell_type, position, shape, radius } => {
+                format!("paint_cells {} {} {} {} {}", cell_type.name(), position.x, position.y, shape.name(), radius)
+            }
+            SimulationAction::Erase { position, shape, radius } => {
+                format!("erase {} {} {} {}", position.x, position.y, shape.name(), radius)
+            }
+            SimulationAction::FloodFill { cell_type, position } => {
+                format!("flood_fill {} {} {}", cell_type.map_or("none", |cell_type| cell_type.name()), position.x, position.y)
+            }
+            SimulationAction::DrawShape { cell_type, shape, from, to, thickness, is_filled } => {
+                format!(
+                    "draw_shape {} {} {} {} {} {} {} {}",
+                    cell_type.map_or("none", |cell_type| cell_type.name()),
+                    shape.name(),
+                    from.x,
+                    from.y,
+                    to.x,
+                    to.y,
+                    thickness,
+                    if *is_filled { "filled" } else { "outlined" }
+                )
+            }
+            SimulationAction::SetPhysics { gravity, wind_particle_coupling } => {
+                format!("set_physics {} {}", gravity, wind_particle_coupling)
+            }
+        }
+    }
+
+    pub fn from_line(line: &str) -> Option<Self> {
+        let mut parts = line.split_whitespace();
+
+        match parts.next()? {
+            "spawn_particles" => Some(SimulationAction::SpawnParticles {
+                cell_type: CellType::from_name(parts.next()?)?,
+                position: Vec2::new(parts.next()?.parse().ok()?, parts.next()?.parse().ok()?),
+                count: parts.next()?.parse().ok()?,
+                shape: BrushShape::from_name(parts.next()?)?,
+                radius: parts.next()?.parse().ok()?
+            }),
+            "paint_cells" => Some(SimulationAction::PaintCells {
+                cell_type: CellType::from_name(parts.next()?)?,
+                position: Vec2::new(parts.next()?.parse().ok()?, parts.next()?.parse().ok()?),
+                shape: BrushShape::from_name(parts.next()?)?,
+                radius: parts.next()?.parse().ok()?
+            }),
+            "erase" => Some(SimulationAction::Erase {
+                position: Vec2::new(parts.next()?.parse().ok()?, parts.next()?.parse().ok()?),
+                shape: BrushShape::from_name(parts.next()?)?,
+                radius: parts.next()?.parse().ok()?
+            }),
+            "flood_fill" => Some(SimulationAction::FloodFill {
+                cell_type: match parts.next()? {
+                    "none" => None,
+                    name => Some(CellType::from_name(name)?)
+                },
+                position: Vec2::new(parts.next()?.parse().ok()?, parts.next()?.parse().ok()?)
+            }),
+            "draw_shape" => Some(SimulationAction::DrawShape {
+                cell_type: match parts.next()? {
+                    "none" => None,
+                    name => Some(CellType::from_name(name)?)
+                },
+                shape: DrawShape::from_name(parts.next()?)?,
+                from: Vec2::new(parts.next()?.parse().ok()?, parts.next()?.parse().ok()?),
+                to: Vec2::new(parts.next()?.parse().ok()?, parts.next()?.parse().ok()?),
+                thickness: parts.next()?.parse().ok()?,
+                is_filled: match parts.next()? {
+                    "filled" => true,
+                    "outlined" => false,
+                    _ => return None
+                }
+            }),
+            "set_physics" => Some(SimulationAction::SetPhysics {
+                gravity: parts.next()?.parse().ok()?,
+                wind_particle_coupling: parts.next()?.parse().ok()?
+            }),
+            _ => None
+        }
+    }
+}
@@ -2,6 +2,19 @@
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum SystemLabels {
+    UpdateInputActions,
+    UpdateEditHistory,
+    SaveAndLoadWorld,
     UpdatePixelSimulation,
-    SimulatePixelSimulation
+    UpdateBrush,
+    UpdateShapeTools,
+    UpdateActionRecording,
+    ApplySimulationActions,
+    ReplaySimulationActions,
+    UpdateWindField,
+    UpdateCellEmitters,
+    UpdateCellDrains,
+    EnforceParticleBudget,
+    SimulatePixelSimulation,
+    CaptureSimulationFrames
 }
\ No newline at end of file
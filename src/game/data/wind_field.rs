@@ -0,0 +1,77 @@
+﻿use bevy::prelude::*;
+use crate::game::constants::{WORLD_SIZE, WIND_FIELD_CELL_SIZE};
+
+const WIND_FIELD_SIZE: usize = WORLD_SIZE / WIND_FIELD_CELL_SIZE;
+
+#[derive(Clone)]
+pub struct WindField {
+    velocities: Vec<Vec2>
+}
+
+impl WindField {
+    pub fn new() -> Self {
+        Self {
+            velocities: vec![Vec2::ZERO; WIND_FIELD_SIZE * WIND_FIELD_SIZE]
+        }
+    }
+
+    pub fn from_velocities(velocities: Vec<Vec2>) -> Option<Self> {
+        if velocities.len() != WIND_FIELD_SIZE * WIND_FIELD_SIZE {
+            return None;
+        }
+
+        Some(Self {
+            velocities
+        })
+    }
+
+    pub fn velocities(&self) -> &[Vec2] {
+        &self.velocities
+    }
+
+    pub fn decay(&mut self, factor: f32) {
+        for velocity in &mut self.velocities {
+            *velocity *= factor;
+        }
+    }
+
+    pub fn add_source(&mut self, world_cell_position: Vec2, velocity: Vec2, radius: f32) {
+        let field_position = world_cell_position / WIND_FIELD_CELL_SIZE as f32;
+        let field_radius = radius / WIND_FIELD_CELL_SIZE as f32;
+
+        for x in 0..WIND_FIELD_SIZE {
+            for y in 0..WIND_FIELD_SIZE {
+                let field_cell_center = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let distance = field_cell_center.distance(field_position);
+
+                if distance < field_radius {
+                    let falloff = 1. - distance / field_radius;
+                    let velocity_in_cell = &mut self.velocities[x + y * WIND_FIELD_SIZE];
+
+                    *velocity_in_cell = velocity_in_cell.lerp(velocity, falloff);
+                }
+            }
+        }
+    }
+
+    pub fn sample(&self, world_cell_position: Vec2) -> Vec2 {
+        let max_field_position = (WIND_FIELD_SIZE - 1) as f32;
+        let field_position = (world_cell_position / WIND_FIELD_CELL_SIZE as f32 - Vec2::ONE * 0.5)
+            .clamp(Vec2::ZERO, Vec2::ONE * max_field_position);
+
+        let x0 = field_position.x.floor() as usize;
+        let y0 = field_position.y.floor() as usize;
+        let x1 = (x0 + 1).min(WIND_FIELD_SIZE - 1);
+        let y1 = (y0 + 1).min(WIND_FIELD_SIZE - 1);
+        let fraction = field_position - Vec2::new(x0 as f32, y0 as f32);
+
+        let top = self.get(x0, y0).lerp(self.get(x1, y0), fraction.x);
+        let bottom = self.get(x0, y1).lerp(self.get(x1, y1), fraction.x);
+
+        top.lerp(bottom, fraction.y)
+    }
+
+    fn get(&self, x: usize, y: usize) -> Vec2 {
+        self.velocities[x + y * WIND_FIELD_SIZE]
+    }
+}
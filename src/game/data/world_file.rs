@@ -0,0 +1,373 @@
+﻿use bevy::prelude::*;
+use crate::game::components::PixelSimulation;
+use crate::game::constants::CHUNK_SIZE;
+use crate::game::data::pixel_simulation::{Cell, Cells, CellType, ChunkCellPosition, ChunkIndex, Particle, Particles};
+use crate::game::data::wind_field::WindField;
+use palette::Srgba;
+use std::fmt;
+
+const WORLD_FILE_MAGIC: &[u8; 4] = b"PXSM";
+const WORLD_FILE_VERSION: u16 = 1;
+const WORLD_SIZE_IN_CHUNKS: u32 = 3;
+const HEADER_SIZE: usize = 18;
+const MAX_CELL_SIZE: usize = 5;
+const PARTICLE_SIZE: usize = 33;
+
+#[derive(Debug)]
+pub enum WorldFileError {
+    InvalidMagic,
+    UnsupportedVersion(u16),
+    MismatchedWorldSize,
+    Corrupted
+}
+
+impl fmt::Display for WorldFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WorldFileError::InvalidMagic => write!(f, "not a world file"),
+            WorldFileError::UnsupportedVersion(version) => write!(f, "unsupported world file version {}", version),
+            WorldFileError::MismatchedWorldSize => write!(f, "world file has a different world size"),
+            WorldFileError::Corrupted => write!(f, "world file is corrupted")
+        }
+    }
+}
+
+impl std::error::Error for WorldFileError {}
+
+pub struct WorldFileContents {
+    pub iteration: u64,
+    pub lost_particle_count: u64,
+    pub gravity: f32,
+    pub wind_particle_coupling: f32,
+    pub wind_field: WindField,
+    pub chunk_cells: Vec<Cells>,
+    pub particles: Vec<Particle>
+}
+
+impl WorldFileContents {
+    pub fn from_pixel_simulation(pixel_simulation: &PixelSimulation) -> Self {
+        Self {
+            iteration: pixel_simulation.iteration,
+            lost_particle_count: pixel_simulation.lost_particle_count,
+            gravity: pixel_simulation.gravity,
+            wind_particle_coupling: pixel_simulation.wind_particle_coupling,
+            wind_field: pixel_simulation.wind_field.clone(),
+            chunk_cells: pixel_simulation.chunks.snapshot_cells(),
+            particles: pixel_simulation.particles.iter().copied().collect()
+        }
+    }
+
+    pub fn apply_to_pixel_simulation(self, pixel_simulation: &mut PixelSimulation, textures: &mut Assets<Texture>) {
+        pixel_simulation.iteration = self.iteration;
+        pixel_simulation.lost_particle_count = self.lost_particle_count;
+        pixel_simulation.gravity = self.gravity;
+        pixel_simulation.wind_particle_coupling = self.wind_particle_coupling;
+        pixel_simulation.wind_field = self.wind_field;
+
+        for (chunk_index, cells) in self.chunk_cells.iter().enumerate() {
+            pixel_simulation.chunks.get_chunk(ChunkIndex(chunk_index)).set_cells(cells, textures);
+        }
+
+        let mut particles = Particles::new();
+
+        for particle in self.particles {
+            particles.add_particle(particle);
+        }
+
+        pixel_simulation.particles = particles;
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut payload = Vec::new();
+
+        payload.extend_from_slice(&self.iteration.to_le_bytes());
+        payload.extend_from_slice(&self.lost_particle_count.to_le_bytes());
+        payload.extend_from_slice(&self.gravity.to_le_bytes());
+        payload.extend_from_slice(&self.wind_particle_coupling.to_le_bytes());
+        payload.extend_from_slice(&(self.wind_field.velocities().len() as u32).to_le_bytes());
+
+        for velocity in self.wind_field.velocities() {
+            write_vec2(&mut payload, *velocity);
+        }
+
+        for cells in &self.chunk_cells {
+            for x in 0..CHUNK_SIZE {
+                for y in 0..CHUNK_SIZE {
+                    match cells.get_cell(ChunkCellPosition(UVec2::new(x as u32, y as u32))) {
+                        Some(cell) => {
+                            payload.push(cell.cell_type.to_id());
+                            write_color(&mut payload, cell.color);
+                        }
+                        None => payload.push(0)
+                    }
+                }
+            }
+        }
+
+        payload.extend_from_slice(&(self.particles.len() as u32).to_le_bytes());
+
+        for particle in &self.particles {
+            payload.push(particle.particle_type.to_id());
+            write_vec2(&mut payload, particle.position);
+            write_vec2(&mut payload, particle.velocity);
+            write_color(&mut payload, particle.color);
+            payload.extend_from_slice(&particle.drag.to_le_bytes());
+            payload.extend_from_slice(&particle.restitution.to_le_bytes());
+            payload.extend_from_slice(&particle.friction.to_le_bytes());
+        }
+
+        let mut bytes = Vec::new();
+
+        bytes.extend_from_slice(WORLD_FILE_MAGIC);
+        bytes.extend_from_slice(&WORLD_FILE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&WORLD_SIZE_IN_CHUNKS.to_le_bytes());
+        bytes.extend_from_slice(&WORLD_SIZE_IN_CHUNKS.to_le_bytes());
+        bytes.extend_from_slice(&(CHUNK_SIZE as u32).to_le_bytes());
+        bytes.extend_from_slice(&miniz_oxide::deflate::compress_to_vec_zlib(&payload, 6));
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WorldFileError> {
+        if bytes.len() < 6 || &bytes[0..4] != WORLD_FILE_MAGIC {
+            return Err(WorldFileError::InvalidMagic);
+        }
+
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+
+        if version != WORLD_FILE_VERSION {
+            return Err(WorldFileError::UnsupportedVersion(version));
+        }
+
+        let mut header_reader = ByteReader::new(bytes.get(6..HEADER_SIZE).ok_or(WorldFileError::Corrupted)?);
+
+        let width_in_chunks = header_reader.read_u32()?;
+        let height_in_chunks = header_reader.read_u32()?;
+        let chunk_size = header_reader.read_u32()?;
+
+        if width_in_chunks != WORLD_SIZE_IN_CHUNKS || height_in_chunks != WORLD_SIZE_IN_CHUNKS || chunk_size != CHUNK_SIZE as u32 {
+            return Err(WorldFileError::MismatchedWorldSize);
+        }
+
+        let payload = miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(&bytes[HEADER_SIZE..], max_payload_size())
+            .map_err(|_| WorldFileError::Corrupted)?;
+        let mut reader = ByteReader::new(&payload);
+
+        let iteration = reader.read_u64()?;
+        let lost_particle_count = reader.read_u64()?;
+        let gravity = reader.read_f32()?;
+        let wind_particle_coupling = reader.read_f32()?;
+        let wind_velocity_count = reader.read_u32()?;
+        let wind_velocities = (0..wind_velocity_count)
+            .map(|_| reader.read_vec2())
+            .collect::<Result<Vec<_>, _>>()?;
+        let wind_field = WindField::from_velocities(wind_velocities).ok_or(WorldFileError::Corrupted)?;
+
+        let mut chunk_cells = Vec::new();
+
+        for _ in 0..(width_in_chunks * height_in_chunks) {
+            let mut cells = Cells::new();
+
+            for x in 0..CHUNK_SIZE {
+                for y in 0..CHUNK_SIZE {
+                    let cell_type_id = reader.read_u8()?;
+
+                    if cell_type_id != 0 {
+                        let cell_type = CellType::from_id(cell_type_id).ok_or(WorldFileError::Corrupted)?;
+                        let color = reader.read_color()?;
+
+                        cells.set_cell(ChunkCellPosition(UVec2::new(x as u32, y as u32)), Some(Cell {
+                            cell_type,
+                            color,
+                            last_iteration_updated: 0
+                        }));
+                    }
+                }
+            }
+
+            chunk_cells.push(cells);
+        }
+
+        let particle_count = reader.read_u32()?;
+        let mut particles = Vec::new();
+
+        for _ in 0..particle_count {
+            let particle_type = CellType::from_id(reader.read_u8()?).ok_or(WorldFileError::Corrupted)?;
+
+            particles.push(Particle {
+                particle_type,
+                position: reader.read_vec2()?,
+                velocity: reader.read_vec2()?,
+                color: reader.read_color()?,
+                drag: reader.read_f32()?,
+                restitution: reader.read_f32()?,
+                friction: reader.read_f32()?
+            });
+        }
+
+        Ok(Self {
+            iteration,
+            lost_particle_count,
+            gravity,
+            wind_particle_coupling,
+            wind_field,
+            chunk_cells,
+            particles
+        })
+    }
+}
+
+// The largest payload a world of this size can have: every cell filled and at most one particle per cell.
+fn max_payload_size() -> usize {
+    let cell_count = (WORLD_SIZE_IN_CHUNKS * WORLD_SIZE_IN_CHUNKS) as usize * CHUNK_SIZE * CHUNK_SIZE;
+    let wind_field_size = 4 + WindField::new().velocities().len() * 8;
+
+    24 + wind_field_size + cell_count * MAX_CELL_SIZE + 4 + cell_count * PARTICLE_SIZE
+}
+
+fn write_color(bytes: &mut Vec<u8>, color: Srgba<u8>) {
+    bytes.extend_from_slice(&[color.red, color.green, color.blue, color.alpha]);
+}
+
+fn write_vec2(bytes: &mut Vec<u8>, vector: Vec2) {
+    bytes.extend_from_slice(&vector.x.to_le_bytes());
+    bytes.extend_from_slice(&vector.y.to_le_bytes());
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            position: 0
+        }
+    }
+
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], WorldFileError> {
+        let end = self.position + N;
+        let slice = self.bytes.get(self.position..end).ok_or(WorldFileError::Corrupted)?;
+
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(slice);
+        self.position = end;
+
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, WorldFileError> {
+        Ok(self.read_bytes::<1>()?[0])
+    }
+
+    fn read_u32(&mut self) -> Result<u32, WorldFileError> {
+        Ok(u32::from_le_bytes(self.read_bytes()?))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, WorldFileError> {
+        Ok(u64::from_le_bytes(self.read_bytes()?))
+    }
+
+    fn read_f32(&mut self) -> Result<f32, WorldFileError> {
+        Ok(f32::from_le_bytes(self.read_bytes()?))
+    }
+
+    fn read_vec2(&mut self) -> Result<Vec2, WorldFileError> {
+        Ok(Vec2::new(self.read_f32()?, self.read_f32()?))
+    }
+
+    fn read_color(&mut self) -> Result<Srgba<u8>, WorldFileError> {
+        let [red, green, blue, alpha] = self.read_bytes::<4>()?;
+
+        Ok(Srgba::new(red, green, blue, alpha))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_contents() -> WorldFileContents {
+        let mut chunk_cells = vec![Cells::new(); (WORLD_SIZE_IN_CHUNKS * WORLD_SIZE_IN_CHUNKS) as usize];
+
+        chunk_cells[4].set_cell(ChunkCellPosition(UVec2::new(3, 5)), Some(Cell {
+            cell_type: CellType::Sand,
+            color: Srgba::new(1, 2, 3, 255),
+            last_iteration_updated: 0
+        }));
+        chunk_cells[8].set_cell(ChunkCellPosition(UVec2::new(63, 0)), Some(Cell {
+            cell_type: CellType::Water,
+            color: Srgba::new(40, 50, 60, 128),
+            last_iteration_updated: 0
+        }));
+
+        let mut wind_field = WindField::new();
+        wind_field.add_source(Vec2::new(96., 96.), Vec2::new(30., -10.), 40.);
+
+        WorldFileContents {
+            iteration: 42,
+            lost_particle_count: 7,
+            gravity: 150.,
+            wind_particle_coupling: 1.25,
+            wind_field,
+            chunk_cells,
+            particles: vec![Particle::new(CellType::Water, Vec2::new(10.5, 20.25), Vec2::new(-3., 4.), Srgba::new(10, 20, 30, 200))]
+        }
+    }
+
+    #[test]
+    fn round_trips_through_bytes() {
+        let contents = test_contents();
+        let loaded = WorldFileContents::from_bytes(&contents.to_bytes()).unwrap();
+
+        assert_eq!(loaded.iteration, contents.iteration);
+        assert_eq!(loaded.lost_particle_count, contents.lost_particle_count);
+        assert_eq!(loaded.gravity, contents.gravity);
+        assert_eq!(loaded.wind_particle_coupling, contents.wind_particle_coupling);
+        assert_eq!(loaded.wind_field.velocities(), contents.wind_field.velocities());
+        assert_eq!(loaded.chunk_cells.len(), contents.chunk_cells.len());
+
+        for (cells, loaded_cells) in contents.chunk_cells.iter().zip(loaded.chunk_cells.iter()) {
+            for x in 0..CHUNK_SIZE {
+                for y in 0..CHUNK_SIZE {
+                    let cell_position = ChunkCellPosition(UVec2::new(x as u32, y as u32));
+
+                    assert_eq!(
+                        loaded_cells.get_cell(cell_position).map(|cell| (cell.cell_type.to_id(), cell.color)),
+                        cells.get_cell(cell_position).map(|cell| (cell.cell_type.to_id(), cell.color))
+                    );
+                }
+            }
+        }
+
+        assert_eq!(loaded.particles.len(), 1);
+
+        let (particle, loaded_particle) = (contents.particles[0], loaded.particles[0]);
+
+        assert_eq!(loaded_particle.particle_type.to_id(), particle.particle_type.to_id());
+        assert_eq!(loaded_particle.position, particle.position);
+        assert_eq!(loaded_particle.velocity, particle.velocity);
+        assert_eq!(loaded_particle.color, particle.color);
+        assert_eq!(loaded_particle.drag, particle.drag);
+        assert_eq!(loaded_particle.restitution, particle.restitution);
+        assert_eq!(loaded_particle.friction, particle.friction);
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        let bytes = test_contents().to_bytes();
+
+        assert!(WorldFileContents::from_bytes(&bytes[..bytes.len() / 2]).is_err());
+        assert!(WorldFileContents::from_bytes(&bytes[..HEADER_SIZE - 1]).is_err());
+    }
+
+    #[test]
+    fn rejects_payloads_larger_than_the_world() {
+        let mut bytes = test_contents().to_bytes()[..HEADER_SIZE].to_vec();
+        bytes.extend_from_slice(&miniz_oxide::deflate::compress_to_vec_zlib(&vec![0; max_payload_size() + 1], 6));
+
+        assert!(matches!(WorldFileContents::from_bytes(&bytes), Err(WorldFileError::Corrupted)));
+    }
+}
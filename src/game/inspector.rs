@@ -0,0 +1,60 @@
+﻿use bevy_egui::egui;
+use bevy_inspector_egui::{Context, Inspectable};
+use crate::game::components::PixelSimulation;
+use crate::game::data::pixel_simulation::CellType;
+use crate::game::data::simulation_action::SimulationAction;
+
+impl Inspectable for PixelSimulation {
+    type Attributes = ();
+
+    // Physics edits are queued as `SimulationAction`s rather than applied here, so they are recorded and replayed.
+    fn ui(&mut self, ui: &mut egui::Ui, _options: Self::Attributes, _context: &Context) -> bool {
+        let moving_chunk_count = self.moved_cell_counts.iter().filter(|count| **count > 0).count();
+
+        egui::Grid::new("pixel_simulation_statistics").show(ui, |ui| {
+            ui.label("Chunks");
+            ui.label(self.chunks.len().to_string());
+            ui.end_row();
+
+            ui.label("Chunks with moving cells");
+            ui.label(moving_chunk_count.to_string());
+            ui.end_row();
+
+            ui.label("Particles");
+            ui.label(self.particles.len().to_string());
+            ui.end_row();
+
+            ui.label("Lost particles");
+            ui.label(self.lost_particle_count.to_string());
+            ui.end_row();
+
+            ui.label("Tick");
+            ui.label(self.iteration.to_string());
+            ui.end_row();
+
+            for cell_type in CellType::ALL.iter() {
+                ui.label(cell_type.name());
+                ui.label(self.chunks.count_cells_of_type(*cell_type).to_string());
+                ui.end_row();
+            }
+        });
+
+        ui.separator();
+
+        let mut gravity = self.gravity;
+        let mut wind_particle_coupling = self.wind_particle_coupling;
+        let mut changed = false;
+
+        changed |= ui.add(egui::Slider::new(&mut gravity, 0.0..=1000.0).text("Gravity")).changed();
+        changed |= ui.add(egui::Slider::new(&mut wind_particle_coupling, 0.0..=2.0).text("Wind coupling")).changed();
+
+        if changed {
+            self.inspector_action = Some(SimulationAction::SetPhysics {
+                gravity,
+                wind_particle_coupling
+            });
+        }
+
+        changed
+    }
+}
@@ -1,6 +1,7 @@
 ﻿pub mod plugin;
 mod resources;
-mod components;
+pub mod components;
 mod systems;
 mod data;
 mod constants;
+mod inspector;
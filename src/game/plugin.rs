@@ -1,27 +1,192 @@
 ﻿use bevy::prelude::*;
+use bevy::input::InputSystem;
 use crate::game::systems::setup::setup;
 use crate::game::systems::simulate_pixel_simulation::simulate_pixel_simulation;
 use crate::game::systems::update_pixel_simulation::update_pixel_simulation;
+use crate::game::systems::update_wind_field::update_wind_field;
+use crate::game::systems::update_cell_emitters::update_cell_emitters;
+use crate::game::systems::update_cell_drains::update_cell_drains;
+use crate::game::systems::enforce_particle_budget::enforce_particle_budget;
+use crate::game::systems::pixel_simulation_timestep::pixel_simulation_timestep;
+use crate::game::systems::update_simulation_control::update_simulation_control;
+use crate::game::systems::update_edit_history::update_edit_history;
+use crate::game::systems::save_and_load_world::save_and_load_world;
+use crate::game::systems::import_dropped_images::import_dropped_images;
+use crate::game::systems::export_world_image::export_world_image;
+use crate::game::systems::apply_simulation_actions::apply_simulation_actions;
+use crate::game::systems::replay_simulation_actions::replay_simulation_actions;
+use crate::game::systems::update_action_recording::update_action_recording;
+use crate::game::systems::capture_simulation_frames::capture_simulation_frames;
+use crate::game::systems::update_frame_capture::update_frame_capture;
+use crate::game::systems::update_brush::update_brush;
+use crate::game::systems::show_material_palette::show_material_palette;
+use crate::game::systems::queue_inspector_actions::queue_inspector_actions;
+use crate::game::systems::update_shape_tools::update_shape_tools;
+use crate::game::systems::draw_shape_preview::draw_shape_preview;
+use crate::game::systems::update_camera::update_camera;
+use crate::game::systems::load_input_bindings::load_input_bindings;
+use crate::game::systems::update_input_actions::update_input_actions;
+use crate::game::systems::show_hovered_cell::show_hovered_cell;
+use crate::game::systems::update_debug_overlay::update_debug_overlay;
+use crate::game::resources::{ParticleBudget, SimulationControl, EditHistory, SimulationRandom, ActionQueue, ActionRecorder, FrameCapture, Brush, ShapePreview, InputActions, DebugOverlay};
+use crate::game::data::input_bindings::InputBindings;
 use crate::game::data::system_labels::SystemLabels;
-use crate::game::constants::PIXEL_SIMULATION_TIMESTEP;
-use bevy::core::FixedTimestep;
 
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(ParticleBudget::default());
+        app.insert_resource(SimulationControl::default());
+        app.insert_resource(EditHistory::default());
+        app.insert_resource(SimulationRandom::default());
+        app.insert_resource(ActionQueue::default());
+        app.insert_resource(ActionRecorder::default());
+        app.insert_resource(FrameCapture::default());
+        app.insert_resource(Brush::default());
+        app.insert_resource(ShapePreview::default());
+        app.insert_resource(InputBindings::default());
+        app.insert_resource(InputActions::default());
+        app.insert_resource(DebugOverlay::default());
+
         app.add_startup_system(setup.system());
+        app.add_startup_system(load_input_bindings.system());
+
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            update_input_actions.system()
+                .label(SystemLabels::UpdateInputActions)
+                .after(InputSystem)
+        );
+
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            update_simulation_control.system()
+                .after(SystemLabels::UpdateInputActions)
+        );
         
-        app.add_system(
-            simulate_pixel_simulation.system()
-                .label(SystemLabels::SimulatePixelSimulation)
-                .with_run_criteria(FixedTimestep::step(PIXEL_SIMULATION_TIMESTEP as f64))
+        app.add_system_set(
+            SystemSet::new()
+                .with_run_criteria(pixel_simulation_timestep.system())
+                .with_system(
+                    replay_simulation_actions.system()
+                        .label(SystemLabels::ReplaySimulationActions)
+                        .before(SystemLabels::UpdateWindField)
+                        .before(SystemLabels::UpdateCellEmitters)
+                        .before(SystemLabels::SimulatePixelSimulation)
+                )
+                .with_system(
+                    update_wind_field.system()
+                        .label(SystemLabels::UpdateWindField)
+                        .before(SystemLabels::SimulatePixelSimulation)
+                )
+                .with_system(
+                    update_cell_emitters.system()
+                        .label(SystemLabels::UpdateCellEmitters)
+                        .before(SystemLabels::SimulatePixelSimulation)
+                )
+                .with_system(
+                    update_cell_drains.system()
+                        .label(SystemLabels::UpdateCellDrains)
+                        .after(SystemLabels::UpdateCellEmitters)
+                        .before(SystemLabels::SimulatePixelSimulation)
+                )
+                .with_system(
+                    enforce_particle_budget.system()
+                        .label(SystemLabels::EnforceParticleBudget)
+                        .after(SystemLabels::UpdateCellDrains)
+                        .before(SystemLabels::SimulatePixelSimulation)
+                )
+                .with_system(
+                    simulate_pixel_simulation.system()
+                        .label(SystemLabels::SimulatePixelSimulation)
+                )
+                .with_system(
+                    capture_simulation_frames.system()
+                        .label(SystemLabels::CaptureSimulationFrames)
+                        .after(SystemLabels::SimulatePixelSimulation)
+                )
         );
             
+        app.add_system(
+            apply_simulation_actions.system()
+                .label(SystemLabels::ApplySimulationActions)
+                .after(SystemLabels::UpdatePixelSimulation)
+                .after(SystemLabels::UpdateShapeTools)
+                .before(SystemLabels::ReplaySimulationActions)
+                .before(SystemLabels::UpdateWindField)
+                .before(SystemLabels::UpdateCellEmitters)
+                .before(SystemLabels::SimulatePixelSimulation)
+        );
+
         app.add_system(
             update_pixel_simulation.system()
                 .label(SystemLabels::UpdatePixelSimulation)
                 .before(SystemLabels::SimulatePixelSimulation)
         );
+
+        app.add_system(
+            update_edit_history.system()
+                .label(SystemLabels::UpdateEditHistory)
+                .before(SystemLabels::UpdatePixelSimulation)
+        );
+
+        app.add_system(
+            save_and_load_world.system()
+                .label(SystemLabels::SaveAndLoadWorld)
+                .after(SystemLabels::UpdateEditHistory)
+                .before(SystemLabels::UpdatePixelSimulation)
+        );
+
+        app.add_system(
+            import_dropped_images.system()
+                .after(SystemLabels::UpdateEditHistory)
+                .before(SystemLabels::UpdatePixelSimulation)
+        );
+
+        app.add_system(export_world_image.system());
+
+        app.add_system(
+            update_action_recording.system()
+                .label(SystemLabels::UpdateActionRecording)
+                .after(SystemLabels::UpdateEditHistory)
+                .before(SystemLabels::UpdatePixelSimulation)
+        );
+
+        app.add_system(update_frame_capture.system());
+
+        app.add_system(
+            update_brush.system()
+                .label(SystemLabels::UpdateBrush)
+                .before(SystemLabels::UpdatePixelSimulation)
+        );
+
+        app.add_system(
+            show_material_palette.system()
+                .before(SystemLabels::UpdateBrush)
+        );
+
+        app.add_system(
+            queue_inspector_actions.system()
+                .before(SystemLabels::ApplySimulationActions)
+        );
+
+        app.add_system(
+            update_shape_tools.system()
+                .label(SystemLabels::UpdateShapeTools)
+                .after(SystemLabels::UpdateBrush)
+                .before(SystemLabels::UpdateEditHistory)
+        );
+
+        app.add_system(
+            draw_shape_preview.system()
+                .after(SystemLabels::UpdateShapeTools)
+        );
+
+        app.add_system(update_camera.system());
+
+        app.add_system(show_hovered_cell.system());
+
+        app.add_system(update_debug_overlay.system());
     }
 }
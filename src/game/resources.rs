@@ -0,0 +1,336 @@
+﻿use bevy::prelude::*;
+use crate::game::constants::{DEFAULT_MAX_PARTICLE_COUNT, DEFAULT_MAX_TICKS_PER_FRAME, MAX_EDIT_HISTORY_LENGTH, DEFAULT_FRAME_CAPTURE_INTERVAL, DEFAULT_BRUSH_RADIUS};
+use crate::game::data::edit_command::EditCommand;
+use crate::game::data::pixel_simulation::{CellType, Chunks};
+use crate::game::data::simulation_action::SimulationAction;
+use crate::game::data::action_recording::ActionRecording;
+use crate::game::data::brush::BrushShape;
+use crate::game::data::shape_drawing::DrawShape;
+use crate::game::data::input_bindings::InputAction;
+use image::codecs::gif::GifEncoder;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::collections::{HashSet, VecDeque};
+use std::fs::File;
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ParticleOverflowPolicy {
+    DropNew,
+    SettleOldest,
+    Merge
+}
+
+pub struct ParticleBudget {
+    pub max_particle_count: usize,
+    pub overflow_policy: ParticleOverflowPolicy
+}
+
+impl Default for ParticleBudget {
+    fn default() -> Self {
+        Self {
+            max_particle_count: DEFAULT_MAX_PARTICLE_COUNT,
+            overflow_policy: ParticleOverflowPolicy::SettleOldest
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DroppedTimePolicy {
+    Discard,
+    CarryOver
+}
+
+pub struct SimulationControl {
+    pub is_paused: bool,
+    pub pending_steps: u32,
+    pub speed: f32,
+    pub max_ticks_per_frame: u32,
+    pub dropped_time_policy: DroppedTimePolicy
+}
+
+impl Default for SimulationControl {
+    fn default() -> Self {
+        Self {
+            is_paused: false,
+            pending_steps: 0,
+            speed: 1.,
+            max_ticks_per_frame: DEFAULT_MAX_TICKS_PER_FRAME,
+            dropped_time_policy: DroppedTimePolicy::Discard
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct EditHistory {
+    undo_stack: VecDeque<EditCommand>,
+    redo_stack: Vec<EditCommand>,
+    pending_edit: Option<EditCommand>
+}
+
+impl EditHistory {
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.pending_edit = None;
+    }
+
+    pub fn begin_edit(&mut self) {
+        self.end_edit();
+        self.pending_edit = Some(EditCommand::default());
+    }
+
+    pub fn pending_edit(&mut self) -> &mut EditCommand {
+        self.pending_edit.get_or_insert_with(EditCommand::default)
+    }
+
+    pub fn end_edit(&mut self) {
+        if let Some(edit_command) = self.pending_edit.take() {
+            if !edit_command.is_empty() {
+                self.undo_stack.push_back(edit_command);
+                self.redo_stack.clear();
+
+                if self.undo_stack.len() > MAX_EDIT_HISTORY_LENGTH {
+                    self.undo_stack.pop_front();
+                }
+            }
+        }
+    }
+
+    pub fn undo(&mut self, chunks: &mut Chunks, textures: &mut Assets<Texture>) -> bool {
+        self.end_edit();
+
+        match self.undo_stack.pop_back() {
+            Some(edit_command) => {
+                edit_command.undo(chunks, textures);
+                self.redo_stack.push(edit_command);
+
+                true
+            }
+            None => false
+        }
+    }
+
+    pub fn redo(&mut self, chunks: &mut Chunks, textures: &mut Assets<Texture>) -> bool {
+        self.end_edit();
+
+        match self.redo_stack.pop() {
+            Some(edit_command) => {
+                edit_command.redo(chunks, textures);
+                self.undo_stack.push_back(edit_command);
+
+                true
+            }
+            None => false
+        }
+    }
+}
+
+pub struct SimulationRandom {
+    seed: u64,
+    rng: StdRng
+}
+
+impl SimulationRandom {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed)
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Self::new(seed);
+    }
+
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+}
+
+impl Default for SimulationRandom {
+    fn default() -> Self {
+        Self::new(rand::thread_rng().gen())
+    }
+}
+
+#[derive(Default)]
+pub struct ActionQueue {
+    pub pending_actions: Vec<SimulationAction>
+}
+
+pub enum ActionRecorder {
+    Idle,
+    // The world the recording started from is only written to disk together with the actions.
+    Recording {
+        recording: ActionRecording,
+        world_bytes: Vec<u8>
+    },
+    Replaying(VecDeque<(u64, SimulationAction)>)
+}
+
+impl ActionRecorder {
+    pub fn is_active(&self) -> bool {
+        !matches!(self, ActionRecorder::Idle)
+    }
+}
+
+impl Default for ActionRecorder {
+    fn default() -> Self {
+        ActionRecorder::Idle
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FrameCaptureFormat {
+    PngSequence,
+    Gif
+}
+
+pub struct FrameCapture {
+    pub interval: u64,
+    pub include_particles: bool,
+    pub format: FrameCaptureFormat,
+    pub is_recording: bool,
+    pub frame_count: u32,
+    pub ticks_since_last_frame: u64,
+    pub directory: PathBuf,
+    pub gif_encoder: Option<GifEncoder<File>>
+}
+
+impl FrameCapture {
+    pub fn start(&mut self, format: FrameCaptureFormat, directory: PathBuf, gif_encoder: Option<GifEncoder<File>>) {
+        self.format = format;
+        self.is_recording = true;
+        self.frame_count = 0;
+        self.ticks_since_last_frame = 0;
+        self.directory = directory;
+        self.gif_encoder = gif_encoder;
+    }
+
+    pub fn stop(&mut self) {
+        self.is_recording = false;
+        self.gif_encoder = None;
+    }
+}
+
+impl Default for FrameCapture {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_FRAME_CAPTURE_INTERVAL,
+            include_particles: true,
+            format: FrameCaptureFormat::PngSequence,
+            is_recording: false,
+            frame_count: 0,
+            ticks_since_last_frame: 0,
+            directory: PathBuf::new(),
+            gif_encoder: None
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    Brush,
+    Fill,
+    Line,
+    Rectangle,
+    Circle
+}
+
+impl Tool {
+    pub fn draw_shape(self) -> Option<DrawShape> {
+        match self {
+            Tool::Line => Some(DrawShape::Line),
+            Tool::Rectangle => Some(DrawShape::Rectangle),
+            Tool::Circle => Some(DrawShape::Circle),
+            Tool::Brush | Tool::Fill => None
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BrushMode {
+    Particles,
+    Cells
+}
+
+pub struct Brush {
+    pub tool: Tool,
+    pub cell_type: CellType,
+    pub secondary_cell_type: CellType,
+    pub radius: u32,
+    pub shape: BrushShape,
+    pub mode: BrushMode,
+    pub is_eraser: bool,
+    pub is_filled: bool
+}
+
+impl Default for Brush {
+    fn default() -> Self {
+        Self {
+            tool: Tool::Brush,
+            cell_type: CellType::Sand,
+            secondary_cell_type: CellType::Water,
+            radius: DEFAULT_BRUSH_RADIUS,
+            shape: BrushShape::Circle,
+            mode: BrushMode::Particles,
+            is_eraser: false,
+            is_filled: true
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ShapePreview {
+    pub action: Option<SimulationAction>
+}
+
+#[derive(Default)]
+pub struct DebugOverlay {
+    pub is_visible: bool
+}
+
+#[derive(Default)]
+pub struct InputActions {
+    pressed: HashSet<InputAction>,
+    just_pressed: HashSet<InputAction>,
+    wheel_steps: f32
+}
+
+impl InputActions {
+    pub fn clear(&mut self) {
+        self.pressed.clear();
+        self.just_pressed.clear();
+        self.wheel_steps = 0.;
+    }
+
+    pub fn set_wheel_steps(&mut self, wheel_steps: f32) {
+        self.wheel_steps = wheel_steps;
+    }
+
+    pub fn press(&mut self, action: InputAction, is_just_pressed: bool) {
+        self.pressed.insert(action);
+
+        if is_just_pressed {
+            self.just_pressed.insert(action);
+        }
+    }
+
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    // How many notches the mouse wheel moved up (positive) or down this frame, if it's bound to `action`.
+    pub fn wheel_steps(&self, action: InputAction) -> f32 {
+        if self.pressed(action) { self.wheel_steps } else { 0. }
+    }
+}
\ No newline at end of file
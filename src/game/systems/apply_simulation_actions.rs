@@ -0,0 +1,35 @@
+﻿use bevy::prelude::*;
+use crate::game::components::PixelSimulation;
+use crate::game::systems::enforce_particle_budget::apply_particle_budget;
+use crate::game::resources::{ActionQueue, ActionRecorder, EditHistory, ParticleBudget, SimulationRandom};
+
+// Runs every frame rather than per tick, so edits show up while paused. The actions are recorded for the next
+// tick, which is where a replay applies them.
+pub fn apply_simulation_actions(
+    mut query: Query<&mut PixelSimulation>,
+    mut action_queue: ResMut<ActionQueue>,
+    mut action_recorder: ResMut<ActionRecorder>,
+    mut simulation_random: ResMut<SimulationRandom>,
+    mut edit_history: ResMut<EditHistory>,
+    mut textures: ResMut<Assets<Texture>>,
+    particle_budget: Res<ParticleBudget>
+) {
+    let pending_actions: Vec<_> = action_queue.pending_actions.drain(..).collect();
+
+    if let ActionRecorder::Replaying(_) = *action_recorder {
+        return;
+    }
+
+    for mut pixel_simulation in query.iter_mut() {
+        let tick = pixel_simulation.iteration.wrapping_add(1);
+
+        if let ActionRecorder::Recording { recording, .. } = &mut *action_recorder {
+            recording.actions.extend(pending_actions.iter().map(|action| (tick, *action)));
+        }
+
+        for action in pending_actions.iter() {
+            action.apply(&mut pixel_simulation, simulation_random.rng(), edit_history.pending_edit(), &mut textures);
+            apply_particle_budget(&mut pixel_simulation, &particle_budget, &mut textures);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+﻿use bevy::prelude::*;
+use crate::game::components::PixelSimulation;
+use crate::game::constants::PIXEL_SIMULATION_TIMESTEP;
+use crate::game::data::frame_capture::{write_png_frame, write_gif_frame};
+use crate::game::data::image_export::render_cell_colors;
+use crate::game::resources::{FrameCapture, FrameCaptureFormat};
+use std::time::Duration;
+
+pub fn capture_simulation_frames(
+    query: Query<&PixelSimulation>,
+    mut frame_capture: ResMut<FrameCapture>
+) {
+    if !frame_capture.is_recording {
+        return;
+    }
+
+    frame_capture.ticks_since_last_frame += 1;
+
+    if frame_capture.ticks_since_last_frame < frame_capture.interval.max(1) {
+        return;
+    }
+
+    frame_capture.ticks_since_last_frame = 0;
+
+    if let Ok(pixel_simulation) = query.single() {
+        let particles = if frame_capture.include_particles { Some(&pixel_simulation.particles) } else { None };
+        let image = render_cell_colors(&pixel_simulation.chunks.snapshot_cells(), particles);
+        let frame_duration = Duration::from_secs_f32(PIXEL_SIMULATION_TIMESTEP * frame_capture.interval.max(1) as f32);
+        let frame_capture = &mut *frame_capture;
+
+        let result = match (frame_capture.format, frame_capture.gif_encoder.as_mut()) {
+            (FrameCaptureFormat::Gif, Some(gif_encoder)) => write_gif_frame(gif_encoder, image, frame_duration),
+            _ => write_png_frame(&frame_capture.directory, frame_capture.frame_count, &image)
+        };
+
+        if let Err(error) = result {
+            error!("Failed to capture frame to {}: {}", frame_capture.directory.display(), error);
+            frame_capture.stop();
+
+            return;
+        }
+
+        frame_capture.frame_count += 1;
+    }
+}
@@ -0,0 +1,54 @@
+﻿use bevy::prelude::*;
+use crate::game::components::PixelSimulation;
+use crate::game::constants::SHAPE_PREVIEW_ALPHA;
+use crate::game::data::cell_colors::cell_swatch_color;
+use crate::game::data::pixel_simulation::WorldCellPosition;
+use crate::game::data::simulation_action::SimulationAction;
+use crate::game::resources::ShapePreview;
+use palette::Srgba;
+use std::collections::{HashMap, HashSet};
+
+// Only the cells that entered or left the preview are written, unless its color changed.
+pub fn draw_shape_preview(
+    mut drawn_previews: Local<HashMap<Entity, (Srgba<u8>, HashSet<IVec2>)>>,
+    mut query: Query<(Entity, &mut PixelSimulation)>,
+    shape_preview: Res<ShapePreview>,
+    mut textures: ResMut<Assets<Texture>>
+) {
+    if !shape_preview.is_changed() {
+        return;
+    }
+
+    let (color, cell_positions) = match shape_preview.action {
+        Some(SimulationAction::DrawShape { cell_type, shape, from, to, thickness, is_filled }) => {
+            let color = match cell_type {
+                Some(cell_type) => {
+                    let color = cell_swatch_color(cell_type);
+
+                    Srgba::new(color.red, color.green, color.blue, SHAPE_PREVIEW_ALPHA)
+                }
+                None => Srgba::new(255, 255, 255, SHAPE_PREVIEW_ALPHA)
+            };
+
+            (color, shape.cell_positions(from.floor().as_i32(), to.floor().as_i32(), thickness, is_filled).into_iter().collect())
+        }
+        _ => (Srgba::new(0, 0, 0, 0), HashSet::new())
+    };
+
+    for (entity, mut pixel_simulation) in query.iter_mut() {
+        let (previous_color, previous_cell_positions) = drawn_previews.remove(&entity)
+            .unwrap_or_else(|| (color, HashSet::new()));
+
+        for cell_position in previous_cell_positions.difference(&cell_positions) {
+            pixel_simulation.chunks.set_overlay_color_at(WorldCellPosition(*cell_position), Srgba::new(0, 0, 0, 0), &mut textures);
+        }
+
+        for cell_position in cell_positions.iter() {
+            if previous_color != color || !previous_cell_positions.contains(cell_position) {
+                pixel_simulation.chunks.set_overlay_color_at(WorldCellPosition(*cell_position), color, &mut textures);
+            }
+        }
+
+        drawn_previews.insert(entity, (color, cell_positions.clone()));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,117 @@
+﻿use bevy::prelude::*;
+use bevy::diagnostic::Diagnostics;
+use crate::game::components::PixelSimulation;
+use crate::game::constants::PARTICLE_COUNT_DIAGNOSTIC;
+use crate::game::data::pixel_simulation::{CellType, Particle, WorldCellPosition};
+use crate::game::resources::{ParticleBudget, ParticleOverflowPolicy};
+use std::collections::HashMap;
+
+pub fn enforce_particle_budget(
+    mut query: Query<&mut PixelSimulation>,
+    particle_budget: Res<ParticleBudget>,
+    mut diagnostics: ResMut<Diagnostics>,
+    mut textures: ResMut<Assets<Texture>>
+) {
+    let mut particle_count = 0;
+
+    for mut pixel_simulation in query.iter_mut() {
+        apply_particle_budget(&mut pixel_simulation, &particle_budget, &mut textures);
+
+        particle_count += pixel_simulation.particles.len();
+    }
+
+    diagnostics.add_measurement(PARTICLE_COUNT_DIAGNOSTIC, particle_count as f64);
+}
+
+// Also applied after every simulation action, so painting particles while paused can't exceed the budget.
+pub fn apply_particle_budget(pixel_simulation: &mut PixelSimulation, particle_budget: &ParticleBudget, textures: &mut Assets<Texture>) {
+    let max_particle_count = particle_budget.max_particle_count;
+
+    if pixel_simulation.particles.len() <= max_particle_count {
+        return;
+    }
+
+    match particle_budget.overflow_policy {
+        ParticleOverflowPolicy::DropNew => {}
+        ParticleOverflowPolicy::SettleOldest => {
+            let overflow_count = pixel_simulation.particles.len() - max_particle_count;
+            let oldest_particles: Vec<_> = pixel_simulation.particles.drain_oldest(overflow_count).collect();
+
+            settle_particles(pixel_simulation, oldest_particles, textures);
+        }
+        ParticleOverflowPolicy::Merge => {
+            let merged_particles = merge_particles(pixel_simulation, max_particle_count);
+
+            settle_particles(pixel_simulation, merged_particles, textures);
+        }
+    }
+
+    let dropped_particle_count = pixel_simulation.particles.len().saturating_sub(max_particle_count);
+
+    pixel_simulation.lost_particle_count += dropped_particle_count as u64;
+    pixel_simulation.particles.truncate(max_particle_count);
+}
+
+// Folds particles sharing a cell into the first one, averaging their velocities, and returns the merged ones
+// so their mass can be settled as cells.
+fn merge_particles(pixel_simulation: &mut PixelSimulation, max_particle_count: usize) -> Vec<Particle> {
+    let merge_key = |particle: &Particle| {
+        let world_cell_position = particle.position.as_i32();
+
+        (world_cell_position.x, world_cell_position.y, particle.particle_type)
+    };
+
+    let mut overflow_count = pixel_simulation.particles.len() - max_particle_count;
+    let mut merge_groups: HashMap<(i32, i32, CellType), (Vec2, u32)> = HashMap::new();
+    let mut is_merged = Vec::with_capacity(pixel_simulation.particles.len());
+
+    for particle in pixel_simulation.particles.iter() {
+        match merge_groups.get_mut(&merge_key(particle)) {
+            Some((velocity_sum, count)) if overflow_count > 0 => {
+                *velocity_sum += particle.velocity;
+                *count += 1;
+                overflow_count -= 1;
+                is_merged.push(true);
+            }
+            Some(_) => is_merged.push(false),
+            None => {
+                merge_groups.insert(merge_key(particle), (particle.velocity, 1));
+                is_merged.push(false);
+            }
+        }
+    }
+
+    let mut merged_particles = Vec::new();
+    let mut is_merged = is_merged.into_iter();
+
+    pixel_simulation.particles.retain_mut(|particle| {
+        if is_merged.next().unwrap_or(false) {
+            merged_particles.push(*particle);
+
+            return false;
+        }
+
+        if let Some((velocity_sum, count)) = merge_groups.remove(&merge_key(particle)) {
+            particle.velocity = velocity_sum / count as f32;
+        }
+
+        true
+    });
+
+    merged_particles
+}
+
+fn settle_particles(pixel_simulation: &mut PixelSimulation, particles: Vec<Particle>, textures: &mut Assets<Texture>) {
+    for particle in particles {
+        let world_cell_position = WorldCellPosition(particle.position.as_i32());
+
+        match pixel_simulation.chunks.find_free_cell_near(world_cell_position) {
+            Some(deposition_position) => {
+                pixel_simulation.chunks.set_cell_at(deposition_position, Some(particle.to_cell()), textures);
+            }
+            None => {
+                pixel_simulation.lost_particle_count += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+﻿use bevy::prelude::*;
+use crate::game::components::PixelSimulation;
+use crate::game::constants::{EXPORT_IMAGE_PATH, EXPORT_MATERIAL_IDS_PATH};
+use crate::game::data::image_export::{render_cell_colors, render_material_ids};
+use crate::game::data::input_bindings::InputAction;
+use crate::game::resources::InputActions;
+
+pub fn export_world_image(
+    query: Query<&PixelSimulation>,
+    input_actions: Res<InputActions>
+) {
+    let should_include_particles = input_actions.just_pressed(InputAction::ExportImageWithParticles);
+
+    if !input_actions.just_pressed(InputAction::ExportImage) && !should_include_particles {
+        return;
+    }
+
+    if let Ok(pixel_simulation) = query.single() {
+        let particles = if should_include_particles { Some(&pixel_simulation.particles) } else { None };
+
+        let chunk_cells = pixel_simulation.chunks.snapshot_cells();
+
+        let result = render_cell_colors(&chunk_cells, particles).save(EXPORT_IMAGE_PATH)
+            .and_then(|_| render_material_ids(&chunk_cells).save(EXPORT_MATERIAL_IDS_PATH));
+
+        match result {
+            Ok(()) => info!("Exported world to {} and {}", EXPORT_IMAGE_PATH, EXPORT_MATERIAL_IDS_PATH),
+            Err(error) => error!("Failed to export world: {}", error)
+        }
+    }
+}
@@ -0,0 +1,54 @@
+﻿use bevy::prelude::*;
+use crate::game::components::PixelSimulation;
+use crate::game::constants::IMPORT_PALETTE_PATH;
+use crate::game::data::image_import::{ColorPalette, ImageImportError, import_image};
+use crate::game::resources::{ActionRecorder, EditHistory};
+use std::fs;
+use std::path::Path;
+
+pub fn import_dropped_images(
+    mut query: Query<&mut PixelSimulation>,
+    mut file_drag_and_drop_events: EventReader<FileDragAndDrop>,
+    mut edit_history: ResMut<EditHistory>,
+    action_recorder: Res<ActionRecorder>,
+    mut textures: ResMut<Assets<Texture>>
+) {
+    for event in file_drag_and_drop_events.iter() {
+        if let FileDragAndDrop::DroppedFile { path_buf, .. } = event {
+            let is_png = path_buf.extension()
+                .and_then(|extension| extension.to_str())
+                .map_or(false, |extension| extension.eq_ignore_ascii_case("png"));
+
+            if !is_png {
+                continue;
+            }
+
+            if action_recorder.is_active() {
+                warn!("Ignoring {}, importing images is disabled while recording or replaying", path_buf.display());
+
+                continue;
+            }
+
+            for mut pixel_simulation in query.iter_mut() {
+                match load_image_and_palette(path_buf) {
+                    Ok((image, palette)) => {
+                        edit_history.begin_edit();
+                        import_image(&image, &palette, &mut pixel_simulation.chunks, edit_history.pending_edit(), &mut textures);
+                        edit_history.end_edit();
+
+                        info!("Imported {}", path_buf.display());
+                    }
+                    Err(error) => error!("Failed to import {}: {}", path_buf.display(), error)
+                }
+            }
+        }
+    }
+}
+
+fn load_image_and_palette(path: &Path) -> Result<(image::RgbaImage, ColorPalette), ImageImportError> {
+    let palette_source = fs::read_to_string(IMPORT_PALETTE_PATH)?;
+    let palette = ColorPalette::parse(&palette_source)?;
+    let image = image::open(path)?.to_rgba8();
+
+    Ok((image, palette))
+}
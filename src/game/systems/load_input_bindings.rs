@@ -0,0 +1,23 @@
+﻿use bevy::prelude::*;
+use crate::game::constants::INPUT_BINDINGS_PATH;
+use crate::game::data::input_bindings::{InputBindings, InputBindingsError};
+use std::fs;
+
+pub fn load_input_bindings(
+    mut input_bindings: ResMut<InputBindings>
+) {
+    match read_input_bindings() {
+        Ok(loaded_input_bindings) => {
+            *input_bindings = loaded_input_bindings.layered_over(InputBindings::default());
+
+            info!("Loaded input bindings from {}", INPUT_BINDINGS_PATH);
+        }
+        Err(error) => error!("Failed to load input bindings from {}, using defaults: {}", INPUT_BINDINGS_PATH, error)
+    }
+}
+
+fn read_input_bindings() -> Result<InputBindings, InputBindingsError> {
+    let source = fs::read_to_string(INPUT_BINDINGS_PATH)?;
+
+    InputBindings::parse(&source)
+}
\ No newline at end of file
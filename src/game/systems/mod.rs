@@ -1,3 +1,28 @@
 ﻿pub mod setup;
 pub mod update_pixel_simulation;
-pub mod simulate_pixel_simulation;
\ No newline at end of file
+pub mod simulate_pixel_simulation;
+pub mod update_wind_field;
+pub mod update_cell_emitters;
+pub mod update_cell_drains;
+pub mod enforce_particle_budget;
+pub mod pixel_simulation_timestep;
+pub mod update_simulation_control;
+pub mod update_edit_history;
+pub mod save_and_load_world;
+pub mod import_dropped_images;
+pub mod export_world_image;
+pub mod apply_simulation_actions;
+pub mod replay_simulation_actions;
+pub mod update_action_recording;
+pub mod capture_simulation_frames;
+pub mod update_frame_capture;
+pub mod update_brush;
+pub mod show_material_palette;
+pub mod queue_inspector_actions;
+pub mod update_shape_tools;
+pub mod draw_shape_preview;
+pub mod update_camera;
+pub mod load_input_bindings;
+pub mod update_input_actions;
+pub mod show_hovered_cell;
+pub mod update_debug_overlay;
\ No newline at end of file
@@ -0,0 +1,73 @@
+﻿use bevy::prelude::*;
+use bevy::ecs::schedule::ShouldRun;
+use bevy::diagnostic::Diagnostics;
+use crate::game::constants::{PIXEL_SIMULATION_TIMESTEP, SKIPPED_TICKS_DIAGNOSTIC};
+use crate::game::resources::{SimulationControl, DroppedTimePolicy};
+
+#[derive(Default)]
+pub struct PixelSimulationTimestepState {
+    accumulator: f64,
+    ticks_this_frame: u32,
+    is_looping: bool
+}
+
+pub fn pixel_simulation_timestep(
+    mut state: Local<PixelSimulationTimestepState>,
+    time: Res<Time>,
+    mut simulation_control: ResMut<SimulationControl>,
+    mut diagnostics: ResMut<Diagnostics>
+) -> ShouldRun {
+    let timestep = PIXEL_SIMULATION_TIMESTEP as f64;
+
+    if !state.is_looping {
+        state.ticks_this_frame = 0;
+
+        if !simulation_control.is_paused {
+            state.accumulator += time.delta_seconds_f64() * simulation_control.speed as f64;
+        }
+    }
+
+    // Requested steps are explicit and don't use up the catch-up budget.
+    if simulation_control.pending_steps > 0 {
+        simulation_control.pending_steps -= 1;
+        state.is_looping = true;
+
+        return ShouldRun::YesAndCheckAgain;
+    }
+
+    if state.ticks_this_frame >= simulation_control.max_ticks_per_frame {
+        let skipped_ticks = match simulation_control.dropped_time_policy {
+            DroppedTimePolicy::Discard => {
+                let skipped_ticks = (state.accumulator / timestep).floor();
+                state.accumulator -= skipped_ticks * timestep;
+
+                skipped_ticks
+            }
+            DroppedTimePolicy::CarryOver => {
+                let max_accumulator = simulation_control.max_ticks_per_frame as f64 * timestep;
+                let skipped_ticks = ((state.accumulator - max_accumulator) / timestep).floor().max(0.);
+                state.accumulator = state.accumulator.min(max_accumulator);
+
+                skipped_ticks
+            }
+        };
+
+        diagnostics.add_measurement(SKIPPED_TICKS_DIAGNOSTIC, skipped_ticks);
+        state.is_looping = false;
+
+        return ShouldRun::No;
+    }
+
+    if !simulation_control.is_paused && state.accumulator >= timestep {
+        state.accumulator -= timestep;
+        state.ticks_this_frame += 1;
+        state.is_looping = true;
+
+        ShouldRun::YesAndCheckAgain
+    } else {
+        diagnostics.add_measurement(SKIPPED_TICKS_DIAGNOSTIC, 0.);
+        state.is_looping = false;
+
+        ShouldRun::No
+    }
+}
@@ -0,0 +1,18 @@
+﻿use bevy::prelude::*;
+use crate::game::components::PixelSimulation;
+use crate::game::resources::ActionQueue;
+
+pub fn queue_inspector_actions(
+    mut query: Query<&mut PixelSimulation>,
+    mut action_queue: ResMut<ActionQueue>
+) {
+    for mut pixel_simulation in query.iter_mut() {
+        if pixel_simulation.inspector_action.is_none() {
+            continue;
+        }
+
+        if let Some(action) = pixel_simulation.inspector_action.take() {
+            action_queue.pending_actions.push(action);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+﻿use bevy::prelude::*;
+use crate::game::components::PixelSimulation;
+use crate::game::systems::enforce_particle_budget::apply_particle_budget;
+use crate::game::resources::{ActionRecorder, EditHistory, ParticleBudget, SimulationRandom};
+
+pub fn replay_simulation_actions(
+    mut query: Query<&mut PixelSimulation>,
+    mut action_recorder: ResMut<ActionRecorder>,
+    mut simulation_random: ResMut<SimulationRandom>,
+    mut edit_history: ResMut<EditHistory>,
+    mut textures: ResMut<Assets<Texture>>,
+    particle_budget: Res<ParticleBudget>
+) {
+    let recorded_actions = match &mut *action_recorder {
+        ActionRecorder::Replaying(recorded_actions) => recorded_actions,
+        _ => return
+    };
+
+    for mut pixel_simulation in query.iter_mut() {
+        let tick = pixel_simulation.iteration.wrapping_add(1);
+
+        while recorded_actions.front().map_or(false, |(action_tick, _)| *action_tick <= tick) {
+            if let Some((_, action)) = recorded_actions.pop_front() {
+                action.apply(&mut pixel_simulation, simulation_random.rng(), edit_history.pending_edit(), &mut textures);
+                apply_particle_budget(&mut pixel_simulation, &particle_budget, &mut textures);
+            }
+        }
+    }
+
+    if recorded_actions.is_empty() {
+        *action_recorder = ActionRecorder::Idle;
+
+        info!("Finished replaying recording");
+    }
+}
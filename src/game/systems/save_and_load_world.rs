@@ -0,0 +1,55 @@
+﻿use bevy::prelude::*;
+use crate::game::components::PixelSimulation;
+use crate::game::constants::WORLD_FILE_PATH;
+use crate::game::data::world_file::WorldFileContents;
+use crate::game::data::input_bindings::InputAction;
+use crate::game::resources::{ActionRecorder, EditHistory, InputActions};
+use std::fs;
+
+pub fn save_and_load_world(
+    mut query: Query<&mut PixelSimulation>,
+    mut edit_history: ResMut<EditHistory>,
+    action_recorder: Res<ActionRecorder>,
+    mut textures: ResMut<Assets<Texture>>,
+    input_actions: Res<InputActions>
+) {
+    if !input_actions.just_pressed(InputAction::SaveWorld) && !input_actions.just_pressed(InputAction::LoadWorld) {
+        return;
+    }
+
+    let mut pixel_simulation = match query.single_mut() {
+        Ok(pixel_simulation) => pixel_simulation,
+        Err(_) => return
+    };
+
+    if input_actions.just_pressed(InputAction::SaveWorld) {
+        let bytes = WorldFileContents::from_pixel_simulation(&pixel_simulation).to_bytes();
+
+        match fs::write(WORLD_FILE_PATH, bytes) {
+            Ok(()) => info!("Saved world to {}", WORLD_FILE_PATH),
+            Err(error) => error!("Failed to save world to {}: {}", WORLD_FILE_PATH, error)
+        }
+    }
+
+    if input_actions.just_pressed(InputAction::LoadWorld) {
+        if action_recorder.is_active() {
+            warn!("Loading a world is disabled while recording or replaying");
+
+            return;
+        }
+
+        let contents = fs::read(WORLD_FILE_PATH)
+            .map_err(|error| error.to_string())
+            .and_then(|bytes| WorldFileContents::from_bytes(&bytes).map_err(|error| error.to_string()));
+
+        match contents {
+            Ok(contents) => {
+                contents.apply_to_pixel_simulation(&mut pixel_simulation, &mut textures);
+                edit_history.clear();
+
+                info!("Loaded world from {}", WORLD_FILE_PATH);
+            }
+            Err(error) => error!("Failed to load world from {}: {}", WORLD_FILE_PATH, error)
+        }
+    }
+}
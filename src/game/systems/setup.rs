@@ -1,15 +1,21 @@
 ﻿use bevy::{
     prelude::*,
+    diagnostic::{Diagnostic, Diagnostics},
 };
-use crate::game::components::{MainCamera, PixelSimulation};
+use crate::game::components::{MainCamera, PixelSimulation, DebugOverlayPart, ChunkActivityHeatmap, ChunkDebugLabel};
 use crate::game::data::pixel_simulation::{Chunk, Chunks, ChunkTexture, ChunkPosition, ChunkIndex};
-use crate::game::constants::WORLD_CHUNK_SIZE;
+use crate::game::constants::{WORLD_CHUNK_SIZE, PARTICLE_COUNT_DIAGNOSTIC, SKIPPED_TICKS_DIAGNOSTIC, DEBUG_OVERLAY_BORDER_WIDTH, DEBUG_OVERLAY_FONT_SIZE};
 
 pub fn setup(
     mut commands: Commands,
     mut textures: ResMut<Assets<Texture>>,
-    mut materials: ResMut<Assets<ColorMaterial>>
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut diagnostics: ResMut<Diagnostics>,
+    asset_server: Res<AssetServer>
 ) {
+    diagnostics.add(Diagnostic::new(PARTICLE_COUNT_DIAGNOSTIC, "particle_count", 20));
+    diagnostics.add(Diagnostic::new(SKIPPED_TICKS_DIAGNOSTIC, "skipped_ticks", 20));
+
     commands.spawn()
         .insert(Name::new("Camera"))
         .insert(MainCamera)
@@ -23,10 +29,14 @@ pub fn setup(
     let mut chunks = Chunks::new(|| {
         let main_texture = ChunkTexture::new(&mut textures, &mut materials);
         let particles_texture = ChunkTexture::new(&mut textures, &mut materials);
+        let overlay_texture = ChunkTexture::new(&mut textures, &mut materials);
 
-        Chunk::new(main_texture, particles_texture)
+        Chunk::new(main_texture, particles_texture, overlay_texture)
     });
     
+    let debug_font = asset_server.load("fonts/FiraMono-Medium.ttf");
+    let debug_border_material = materials.add(Color::rgba(1., 0., 1., 0.8).into());
+
     commands.spawn()
         .insert(Name::new("Pixel Simulation"))
         .insert(PixelSimulation::new(chunks.clone()))
@@ -59,8 +69,81 @@ pub fn setup(
                                     transform: Transform::from_translation(Vec3::new(0., 0., -1.)),
                                     ..Default::default()
                                 });
+
+                            child_builder.spawn()
+                                .insert_bundle(SpriteBundle {
+                                    material: (*chunk.get_overlay_texture().get_material_handle()).clone(),
+                                    sprite: Sprite::new(Vec2::ONE * WORLD_CHUNK_SIZE),
+                                    transform: Transform::from_translation(Vec3::new(0., 0., 1.)),
+                                    ..Default::default()
+                                });
+
+                            spawn_chunk_debug_overlay(child_builder, chunk_position, &debug_font, &debug_border_material, &mut materials);
                         });
                 }
             }
         });
 }
+
+fn spawn_chunk_debug_overlay(
+    child_builder: &mut ChildBuilder,
+    chunk_position: ChunkPosition,
+    font: &Handle<Font>,
+    border_material: &Handle<ColorMaterial>,
+    materials: &mut Assets<ColorMaterial>
+) {
+    let chunk_index = ChunkIndex::from_chunk_position(chunk_position);
+    let hidden = Visible { is_visible: false, is_transparent: true };
+    let half_size = WORLD_CHUNK_SIZE / 2.;
+
+    child_builder.spawn()
+        .insert(DebugOverlayPart)
+        .insert(ChunkActivityHeatmap { chunk_index })
+        .insert_bundle(SpriteBundle {
+            material: materials.add(Color::rgba(1., 0., 0., 0.).into()),
+            sprite: Sprite::new(Vec2::ONE * WORLD_CHUNK_SIZE),
+            transform: Transform::from_translation(Vec3::new(0., 0., 2.)),
+            visible: hidden.clone(),
+            ..Default::default()
+        });
+
+    let borders = [
+        (Vec2::new(-half_size, 0.), Vec2::new(DEBUG_OVERLAY_BORDER_WIDTH, WORLD_CHUNK_SIZE)),
+        (Vec2::new(half_size, 0.), Vec2::new(DEBUG_OVERLAY_BORDER_WIDTH, WORLD_CHUNK_SIZE)),
+        (Vec2::new(0., half_size), Vec2::new(WORLD_CHUNK_SIZE, DEBUG_OVERLAY_BORDER_WIDTH)),
+        (Vec2::new(0., -half_size), Vec2::new(WORLD_CHUNK_SIZE, DEBUG_OVERLAY_BORDER_WIDTH))
+    ];
+
+    for (position, size) in borders.iter() {
+        child_builder.spawn()
+            .insert(DebugOverlayPart)
+            .insert_bundle(SpriteBundle {
+                material: border_material.clone(),
+                sprite: Sprite::new(*size),
+                transform: Transform::from_translation(position.extend(3.)),
+                visible: hidden.clone(),
+                ..Default::default()
+            });
+    }
+
+    child_builder.spawn()
+        .insert(DebugOverlayPart)
+        .insert(ChunkDebugLabel { chunk_index })
+        .insert_bundle(Text2dBundle {
+            text: Text::with_section(
+                format!("chunk {}, {}", chunk_position.x, chunk_position.y),
+                TextStyle {
+                    font: font.clone(),
+                    font_size: DEBUG_OVERLAY_FONT_SIZE,
+                    color: Color::rgba(1., 0., 1., 0.9)
+                },
+                TextAlignment {
+                    vertical: VerticalAlign::Top,
+                    horizontal: HorizontalAlign::Left
+                }
+            ),
+            transform: Transform::from_translation(Vec3::new(-half_size + 4., half_size - 4., 3.)),
+            visible: hidden,
+            ..Default::default()
+        });
+}
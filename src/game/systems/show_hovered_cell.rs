@@ -0,0 +1,78 @@
+﻿use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use crate::game::components::{MainCamera, PixelSimulation};
+use crate::game::data::coordinates::cursor_to_cell;
+use crate::game::data::pixel_simulation::WorldCellPosition;
+
+pub fn show_hovered_cell(
+    egui_context: Res<EguiContext>,
+    main_camera_query: Query<&GlobalTransform, With<MainCamera>>,
+    pixel_simulation_query: Query<(&PixelSimulation, &GlobalTransform)>,
+    windows: Res<Windows>,
+    mut hovered_cell_position: Local<Option<IVec2>>
+) {
+    let window = windows.get_primary().unwrap();
+
+    if let (Ok(camera_transform), Ok((pixel_simulation, pixel_simulation_transform))) = (main_camera_query.single(), pixel_simulation_query.single()) {
+        // Keep showing the last hovered cell while the pointer is over the UI, so the window can be read.
+        if !egui_context.ctx().wants_pointer_input() {
+            if let Some(cell_position) = cursor_to_cell(window, camera_transform, pixel_simulation_transform) {
+                *hovered_cell_position = Some(cell_position.floor().as_i32());
+            }
+        }
+
+        egui::Window::new("Cell").show(egui_context.ctx(), |ui| {
+            let world_cell_position = match *hovered_cell_position {
+                Some(world_cell_position) => WorldCellPosition(world_cell_position),
+                None => {
+                    ui.label("No cell hovered");
+                    return;
+                }
+            };
+
+            let (chunk_index, chunk_cell_position) = match world_cell_position.to_chunk_cell_position() {
+                Some(chunk_cell_position) => chunk_cell_position,
+                None => {
+                    ui.label(format!("Outside of the world at {}, {}", world_cell_position.x, world_cell_position.y));
+                    return;
+                }
+            };
+
+            let chunk_position = chunk_index.to_chunk_position();
+            let cell = pixel_simulation.chunks.get_cell_at(world_cell_position);
+
+            egui::Grid::new("hovered_cell").show(ui, |ui| {
+                ui.label("World");
+                ui.label(format!("{}, {}", world_cell_position.x, world_cell_position.y));
+                ui.end_row();
+
+                ui.label("Chunk");
+                ui.label(format!("{}, {} (index {})", chunk_position.x, chunk_position.y, *chunk_index));
+                ui.end_row();
+
+                ui.label("Cell");
+                ui.label(format!("{}, {}", chunk_cell_position.x, chunk_cell_position.y));
+                ui.end_row();
+
+                ui.label("Material");
+                ui.label(cell.map_or("empty", |cell| cell.cell_type.name()));
+                ui.end_row();
+
+                if let Some(cell) = cell {
+                    ui.label("Color");
+                    ui.horizontal(|ui| {
+                        let (rect, _) = ui.allocate_exact_size(egui::vec2(16., 16.), egui::Sense::hover());
+
+                        ui.painter().rect_filled(rect, 2., egui::Color32::from_rgba_unmultiplied(cell.color.red, cell.color.green, cell.color.blue, cell.color.alpha));
+                        ui.label(format!("#{:02x}{:02x}{:02x}{:02x}", cell.color.red, cell.color.green, cell.color.blue, cell.color.alpha));
+                    });
+                    ui.end_row();
+
+                    ui.label("Last updated");
+                    ui.label(format!("tick {}", cell.last_iteration_updated));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,57 @@
+﻿use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use crate::game::constants::MAX_BRUSH_RADIUS;
+use crate::game::data::brush::BrushShape;
+use crate::game::data::cell_colors::cell_swatch_color;
+use crate::game::data::pixel_simulation::CellType;
+use crate::game::resources::{Brush, BrushMode, Tool};
+
+pub fn show_material_palette(
+    egui_context: Res<EguiContext>,
+    mut brush: ResMut<Brush>
+) {
+    egui::Window::new("Materials").show(egui_context.ctx(), |ui| {
+        ui.label("Left click places the primary material, right click the secondary one");
+
+        egui::Grid::new("materials").show(ui, |ui| {
+            for cell_type in CellType::ALL.iter().copied() {
+                let color = cell_swatch_color(cell_type);
+                let (rect, _) = ui.allocate_exact_size(egui::vec2(16., 16.), egui::Sense::hover());
+
+                ui.painter().rect_filled(rect, 2., egui::Color32::from_rgb(color.red, color.green, color.blue));
+                ui.label(cell_type.name());
+                ui.selectable_value(&mut brush.cell_type, cell_type, "primary");
+                ui.selectable_value(&mut brush.secondary_cell_type, cell_type, "secondary");
+                ui.end_row();
+            }
+        });
+
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label("Tool");
+            ui.selectable_value(&mut brush.tool, Tool::Brush, "brush");
+            ui.selectable_value(&mut brush.tool, Tool::Fill, "fill");
+            ui.selectable_value(&mut brush.tool, Tool::Line, "line");
+            ui.selectable_value(&mut brush.tool, Tool::Rectangle, "rectangle");
+            ui.selectable_value(&mut brush.tool, Tool::Circle, "circle");
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Mode");
+            ui.selectable_value(&mut brush.mode, BrushMode::Particles, "particles");
+            ui.selectable_value(&mut brush.mode, BrushMode::Cells, "cells");
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Shape");
+            ui.selectable_value(&mut brush.shape, BrushShape::Circle, BrushShape::Circle.name());
+            ui.selectable_value(&mut brush.shape, BrushShape::Square, BrushShape::Square.name());
+        });
+
+        ui.add(egui::Slider::new(&mut brush.radius, 0..=MAX_BRUSH_RADIUS).text("Radius"));
+        ui.label("Shift + mouse wheel changes the radius, the mouse wheel alone zooms");
+        ui.checkbox(&mut brush.is_eraser, "Eraser");
+        ui.checkbox(&mut brush.is_filled, "Filled shapes");
+    });
+}
\ No newline at end of file
@@ -1,20 +1,26 @@
 ﻿use bevy::prelude::*;
 use crate::game::components::{PixelSimulation};
-use crate::game::constants::{CHUNK_SIZE, PIXEL_SIMULATION_TIMESTEP};
-use crate::game::data::pixel_simulation::{Cell, CellType, ChunkPosition, ChunkCellPosition, ChunkIndex};
-use std::num::Wrapping;
+use crate::game::constants::{CHUNK_SIZE, WORLD_SIZE, PIXEL_SIMULATION_TIMESTEP, PARTICLE_DEPOSITION_RADIUS, PARTICLE_BOUNCE_MIN_SPEED, WIND_LIFT_THRESHOLD, WIND_LIFT_CHANCE, WIND_DRIFT_THRESHOLD};
+use crate::game::data::pixel_simulation::{CellType, ChunkPosition, ChunkCellPosition, ChunkIndex, Particle, WorldCellPosition};
+use crate::game::data::cell_traversal::CellTraversal;
+use crate::game::resources::SimulationRandom;
+use rand::Rng;
 
 pub fn simulate_pixel_simulation(
     mut query: Query<&mut PixelSimulation>,
-    mut iteration: Local<Wrapping<u64>>,
-    mut textures: ResMut<Assets<Texture>>
+    mut textures: ResMut<Assets<Texture>>,
+    mut simulation_random: ResMut<SimulationRandom>
 ) {
-    *iteration += Wrapping(1);
-    // println!("{}", *iteration);
-    
-    let is_even_iteration = iteration.0 % 2 == 0;
+    let rng = simulation_random.rng();
 
     for mut pixel_simulation in query.iter_mut() {
+        pixel_simulation.iteration = pixel_simulation.iteration.wrapping_add(1);
+        pixel_simulation.moved_cell_counts = [0; 9];
+        // println!("{}", pixel_simulation.iteration);
+
+        let iteration = pixel_simulation.iteration;
+        let is_even_iteration = iteration % 2 == 0;
+
         let horizontal_range_normal = 0..3;
         let horizontal_range = if is_even_iteration {
             itertools::Either::Left(horizontal_range_normal)
@@ -38,9 +44,9 @@ pub fn simulate_pixel_simulation(
                         let chunk_cell_position = ChunkCellPosition(UVec2::new(x as u32, y as u32));
 
                         if let Some(cell_container) = pixel_simulation.chunks.get_chunk(current_chunk_index).get_cell(chunk_cell_position) {
-                            if cell_container.last_iteration_updated != iteration.0 {
+                            if cell_container.last_iteration_updated != iteration {
                                 let mut cell_container = cell_container;
-                                cell_container.last_iteration_updated = iteration.0;
+                                cell_container.last_iteration_updated = iteration;
 
                                 let try_move_offset = |cell_offset: IVec2, pixel_simulation: &mut PixelSimulation, textures: &mut Assets<Texture>| -> bool {
                                     let offseted_cell_position = chunk_cell_position.as_i32() + cell_offset;
@@ -103,25 +109,46 @@ pub fn simulate_pixel_simulation(
                                     false
                                 };
 
-                                let slide_direction = if is_even_iteration { -1 } else { 1 };
+                                let world_cell_position = (*current_chunk_position * (CHUNK_SIZE as u32)) + *chunk_cell_position;
+                                let wind = pixel_simulation.wind_field.sample(world_cell_position.as_f32());
+
+                                let slide_direction = if cell_container.cell_type == CellType::Water && wind.x.abs() > WIND_DRIFT_THRESHOLD {
+                                    wind.x.signum() as i32
+                                } else if is_even_iteration { -1 } else { 1 };
 
-                                match cell_container.cell_type {
+                                let wind_lift_chance = (wind.length() - WIND_LIFT_THRESHOLD) * WIND_LIFT_CHANCE;
+                                let cell_above_position = WorldCellPosition(world_cell_position.as_i32() + IVec2::new(0, -1));
+                                let is_exposed = cell_above_position.is_in_world() && pixel_simulation.chunks.get_cell_at(cell_above_position).is_none();
+
+                                if cell_container.cell_type == CellType::Sand && is_exposed && wind_lift_chance > 0. && rng.gen::<f32>() < wind_lift_chance {
+                                    pixel_simulation.chunks.get_chunk(current_chunk_index).set_cell(chunk_cell_position, None, &mut textures);
+                                    pixel_simulation.particles.add_particle(Particle::new(cell_container.cell_type, world_cell_position.as_f32(), wind, cell_container.color));
+                                    pixel_simulation.moved_cell_counts[*current_chunk_index] += 1;
+
+                                    continue;
+                                }
+
+                                let has_moved = match cell_container.cell_type {
                                     CellType::Sand => {
-                                        if try_switch_if(IVec2::new(0, 1), &[CellType::Water], &mut pixel_simulation, &mut textures) {}
-                                        else if try_switch_if(IVec2::new(slide_direction, 1), &[CellType::Water], &mut pixel_simulation, &mut textures) {}
-                                        else if try_switch_if(IVec2::new(-slide_direction, 1), &[CellType::Water], &mut pixel_simulation, &mut textures) {}
-                                        else { pixel_simulation.chunks.get_chunk(current_chunk_index).set_cell(chunk_cell_position, Some(cell_container), &mut textures); }
+                                        try_switch_if(IVec2::new(0, 1), &[CellType::Water], &mut pixel_simulation, &mut textures)
+                                            || try_switch_if(IVec2::new(slide_direction, 1), &[CellType::Water], &mut pixel_simulation, &mut textures)
+                                            || try_switch_if(IVec2::new(-slide_direction, 1), &[CellType::Water], &mut pixel_simulation, &mut textures)
                                     }
                                     CellType::Water => {
-                                        if try_move_offset(IVec2::new(0, 1), &mut pixel_simulation, &mut textures) {}
-                                        else if try_move_offset(IVec2::new(slide_direction, 1), &mut pixel_simulation, &mut textures) {}
-                                        else if try_move_offset(IVec2::new(-slide_direction, 1), &mut pixel_simulation, &mut textures) {}
-                                        else if try_move_offset(IVec2::new(slide_direction, 0), &mut pixel_simulation, &mut textures) {}
-                                        else if try_move_offset(IVec2::new(-slide_direction, 0), &mut pixel_simulation, &mut textures) {}
-                                        else if try_move_offset(IVec2::new(slide_direction * 2, 0), &mut pixel_simulation, &mut textures) {}
-                                        else if try_move_offset(IVec2::new(-slide_direction * 2, 0), &mut pixel_simulation, &mut textures) {}
-                                        else { pixel_simulation.chunks.get_chunk(current_chunk_index).set_cell(chunk_cell_position, Some(cell_container), &mut textures); }
+                                        try_move_offset(IVec2::new(0, 1), &mut pixel_simulation, &mut textures)
+                                            || try_move_offset(IVec2::new(slide_direction, 1), &mut pixel_simulation, &mut textures)
+                                            || try_move_offset(IVec2::new(-slide_direction, 1), &mut pixel_simulation, &mut textures)
+                                            || try_move_offset(IVec2::new(slide_direction, 0), &mut pixel_simulation, &mut textures)
+                                            || try_move_offset(IVec2::new(-slide_direction, 0), &mut pixel_simulation, &mut textures)
+                                            || try_move_offset(IVec2::new(slide_direction * 2, 0), &mut pixel_simulation, &mut textures)
+                                            || try_move_offset(IVec2::new(-slide_direction * 2, 0), &mut pixel_simulation, &mut textures)
                                     }
+                                };
+
+                                if has_moved {
+                                    pixel_simulation.moved_cell_counts[*current_chunk_index] += 1;
+                                } else {
+                                    pixel_simulation.chunks.get_chunk(current_chunk_index).set_cell(chunk_cell_position, Some(cell_container), &mut textures);
                                 }
                             }
                         }
@@ -142,41 +169,84 @@ pub fn simulate_pixel_simulation(
         }
 
         let mut particles = pixel_simulation.particles.clone();
+        let gravity = pixel_simulation.gravity;
+        let wind_particle_coupling = pixel_simulation.wind_particle_coupling;
 
         particles.retain_mut(|particle| {
-            particle.velocity.y += 200. * PIXEL_SIMULATION_TIMESTEP;
-            particle.position += particle.velocity * PIXEL_SIMULATION_TIMESTEP;
-            particle.position = particle.position.clamp(Vec2::new(0., 0.), Vec2::new((3 * CHUNK_SIZE - 1) as f32, (3 * CHUNK_SIZE - 1) as f32));
+            let wind = pixel_simulation.wind_field.sample(particle.position);
+
+            particle.velocity.y += gravity * PIXEL_SIMULATION_TIMESTEP;
+            particle.velocity += wind * wind_particle_coupling * PIXEL_SIMULATION_TIMESTEP;
+            particle.velocity -= particle.velocity * (particle.drag * PIXEL_SIMULATION_TIMESTEP).min(1.);
+
+            let max_position = Vec2::new((WORLD_SIZE - 1) as f32, (WORLD_SIZE - 1) as f32);
+            let start_position = particle.position.clamp(Vec2::new(0., 0.), max_position);
+            particle.position = start_position + particle.velocity * PIXEL_SIMULATION_TIMESTEP;
+            particle.position = particle.position.clamp(Vec2::new(0., 0.), max_position);
 
-            let world_cell_position = particle.position.as_u32();
-            let chunk_position = ChunkPosition(((world_cell_position) / (CHUNK_SIZE as u32)).clamp(UVec2::new(0, 0), UVec2::new(2, 2)));
-            let chunk_cell_position = ChunkCellPosition(world_cell_position - (*chunk_position * (CHUNK_SIZE as u32)));
-            let chunk_index = ChunkIndex::from_chunk_position(chunk_position);
+            let mut last_free_cell_position = None;
+            let mut hit_cell_position = None;
 
-            if (chunk_position.y == 2 && (*chunk_cell_position).y == (CHUNK_SIZE-1) as u32) || pixel_simulation.chunks.get_chunk(chunk_index).get_cell(chunk_cell_position).is_some() {
-                let offseted_cell_position = chunk_cell_position.as_i32() + IVec2::new(0, -1);
-                let chunk_index_offset = ((offseted_cell_position.as_f32()) / (CHUNK_SIZE as f32)).floor().as_i32();
-                let target_chunk_position = chunk_position.as_i32() + chunk_index_offset;
+            for cell_position in CellTraversal::new(start_position, particle.position) {
+                if pixel_simulation.chunks.get_cell_at(cell_position).is_some() {
+                    hit_cell_position = Some(cell_position);
+                    break;
+                }
+
+                last_free_cell_position = Some(cell_position);
+            }
 
+            let is_on_world_bottom = particle.position.as_i32().y == (WORLD_SIZE - 1) as i32;
 
-                if target_chunk_position.x >= 0 && target_chunk_position.x < 3 && target_chunk_position.y >= 0 && target_chunk_position.y < 3 {
-                    let target_chunk_position = ChunkPosition(target_chunk_position.as_u32());
-                    let target_chunk_index = ChunkIndex::from_chunk_position(target_chunk_position);
-                    let target_chunk_cell_position = ChunkCellPosition((offseted_cell_position - (chunk_index_offset * (CHUNK_SIZE as i32))).as_u32());
+            if hit_cell_position.is_some() || is_on_world_bottom {
+                let mut has_bounced = false;
 
-                    if pixel_simulation.chunks.get_chunk(target_chunk_index).get_cell(target_chunk_cell_position).is_none() {
-                        pixel_simulation.chunks.get_chunk(target_chunk_index).set_cell(target_chunk_cell_position, Some(Cell {
-                            cell_type: particle.particle_type,
-                            color: particle.color,
-                            last_iteration_updated: 0
-                        }), &mut textures);
+                if let Some(last_free_cell_position) = last_free_cell_position {
+                    let hit_normal = match hit_cell_position {
+                        Some(hit_cell_position) => (*last_free_cell_position - *hit_cell_position).as_f32(),
+                        None => Vec2::new(0., -1.)
+                    };
 
+                    let normal_speed = particle.velocity.dot(hit_normal);
+
+                    if -normal_speed * particle.restitution > PARTICLE_BOUNCE_MIN_SPEED {
+                        let normal_velocity = hit_normal * normal_speed;
+                        let tangent_velocity = particle.velocity - normal_velocity;
+
+                        particle.velocity = tangent_velocity * (1. - particle.friction) - normal_velocity * particle.restitution;
+                        particle.position = last_free_cell_position.as_f32() + Vec2::ONE * 0.5;
+                        has_bounced = true;
                     }
                 }
 
-                return false;
+                if !has_bounced {
+                    let deposition_position = last_free_cell_position
+                        .or_else(|| {
+                            let back_tracked_end_position = start_position - particle.velocity.normalize_or_zero() * PARTICLE_DEPOSITION_RADIUS as f32;
+
+                            CellTraversal::new(start_position, back_tracked_end_position)
+                                .take_while(|cell_position| cell_position.is_in_world())
+                                .find(|cell_position| pixel_simulation.chunks.get_cell_at(*cell_position).is_none())
+                        })
+                        .or_else(|| pixel_simulation.chunks.find_free_cell_near(WorldCellPosition(start_position.as_i32())))
+                        .filter(|deposition_position| deposition_position.is_in_world());
+
+                    match deposition_position {
+                        Some(deposition_position) => {
+                            pixel_simulation.chunks.set_cell_at(deposition_position, Some(particle.to_cell()), &mut textures);
+                        }
+                        None => {
+                            pixel_simulation.lost_particle_count += 1;
+                        }
+                    }
+
+                    return false;
+                }
             }
 
+            let world_cell_position = WorldCellPosition(particle.position.as_i32());
+            let (chunk_index, chunk_cell_position) = world_cell_position.to_chunk_cell_position().unwrap();
+
             pixel_simulation.chunks.get_chunk(chunk_index).particles_texture.set_color(chunk_cell_position, particle.color, &mut textures);
 
             true
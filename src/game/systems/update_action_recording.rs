@@ -0,0 +1,88 @@
+﻿use bevy::prelude::*;
+use crate::game::components::{PixelSimulation, CellEmitter};
+use crate::game::constants::{RECORDING_WORLD_PATH, RECORDING_ACTIONS_PATH};
+use crate::game::data::action_recording::ActionRecording;
+use crate::game::data::world_file::WorldFileContents;
+use crate::game::data::input_bindings::InputAction;
+use crate::game::resources::{ActionQueue, ActionRecorder, EditHistory, InputActions, SimulationRandom};
+use rand::Rng;
+use std::fs;
+
+pub fn update_action_recording(
+    mut query: Query<&mut PixelSimulation>,
+    mut cell_emitter_query: Query<&mut CellEmitter>,
+    mut action_queue: ResMut<ActionQueue>,
+    mut action_recorder: ResMut<ActionRecorder>,
+    mut simulation_random: ResMut<SimulationRandom>,
+    mut edit_history: ResMut<EditHistory>,
+    mut textures: ResMut<Assets<Texture>>,
+    input_actions: Res<InputActions>
+) {
+    let mut pixel_simulation = match query.single_mut() {
+        Ok(pixel_simulation) => pixel_simulation,
+        Err(_) => return
+    };
+
+    if input_actions.just_pressed(InputAction::ToggleRecording) {
+        match &*action_recorder {
+            ActionRecorder::Recording { recording, world_bytes } => {
+                let result = fs::write(RECORDING_WORLD_PATH, world_bytes)
+                    .and_then(|()| fs::write(RECORDING_ACTIONS_PATH, recording.to_text()));
+
+                match result {
+                    Ok(()) => info!("Saved recording to {} and {}", RECORDING_WORLD_PATH, RECORDING_ACTIONS_PATH),
+                    Err(error) => error!("Failed to save recording to {} and {}: {}", RECORDING_WORLD_PATH, RECORDING_ACTIONS_PATH, error)
+                }
+
+                *action_recorder = ActionRecorder::Idle;
+            }
+            ActionRecorder::Replaying(_) => warn!("Recording is disabled while replaying"),
+            ActionRecorder::Idle => {
+                let seed = rand::thread_rng().gen();
+
+                for mut cell_emitter in cell_emitter_query.iter_mut() {
+                    cell_emitter.reset_pending_emissions();
+                }
+
+                simulation_random.reseed(seed);
+                action_queue.pending_actions.clear();
+                *action_recorder = ActionRecorder::Recording {
+                    recording: ActionRecording::new(seed),
+                    world_bytes: WorldFileContents::from_pixel_simulation(&pixel_simulation).to_bytes()
+                };
+
+                info!("Started recording with seed {}", seed);
+            }
+        }
+    } else if input_actions.just_pressed(InputAction::ReplayRecording) {
+        if let ActionRecorder::Recording { .. } = *action_recorder {
+            warn!("Replaying is disabled while recording, stop the recording first");
+            return;
+        }
+
+        let contents = fs::read(RECORDING_WORLD_PATH)
+            .map_err(|error| error.to_string())
+            .and_then(|bytes| WorldFileContents::from_bytes(&bytes).map_err(|error| error.to_string()));
+        let recording = fs::read_to_string(RECORDING_ACTIONS_PATH)
+            .map_err(|error| error.to_string())
+            .and_then(|text| ActionRecording::parse(&text).ok_or_else(|| String::from("invalid action recording")));
+
+        match contents.and_then(|contents| recording.map(|recording| (contents, recording))) {
+            Ok((contents, recording)) => {
+                contents.apply_to_pixel_simulation(&mut pixel_simulation, &mut textures);
+
+                for mut cell_emitter in cell_emitter_query.iter_mut() {
+                    cell_emitter.reset_pending_emissions();
+                }
+
+                edit_history.clear();
+                simulation_random.reseed(recording.seed);
+                action_queue.pending_actions.clear();
+                *action_recorder = ActionRecorder::Replaying(recording.actions.into_iter().collect());
+
+                info!("Replaying recording with seed {}", recording.seed);
+            }
+            Err(error) => error!("Failed to load recording: {}", error)
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,75 @@
+﻿use bevy::prelude::*;
+use bevy_egui::EguiContext;
+use crate::game::constants::MAX_BRUSH_RADIUS;
+use crate::game::data::brush::BrushShape;
+use crate::game::components::{MainCamera, PixelSimulation};
+use crate::game::data::coordinates::cursor_to_cell;
+use crate::game::data::input_bindings::InputAction;
+use crate::game::data::pixel_simulation::{CellType, WorldCellPosition};
+use crate::game::resources::{Brush, BrushMode, InputActions, Tool};
+
+pub fn update_brush(
+    mut brush: ResMut<Brush>,
+    egui_context: Res<EguiContext>,
+    main_camera_query: Query<&GlobalTransform, With<MainCamera>>,
+    pixel_simulation_query: Query<(&PixelSimulation, &GlobalTransform)>,
+    windows: Res<Windows>,
+    input_actions: Res<InputActions>
+) {
+    let is_pointer_over_ui = egui_context.ctx().wants_pointer_input();
+    let resize_steps = input_actions.wheel_steps(InputAction::ResizeBrush);
+
+    if !is_pointer_over_ui && resize_steps != 0. {
+        brush.radius = (brush.radius as f32 + resize_steps).clamp(0., MAX_BRUSH_RADIUS as f32) as u32;
+    }
+
+    let tool_actions = [
+        (InputAction::FillTool, Tool::Fill),
+        (InputAction::LineTool, Tool::Line),
+        (InputAction::RectangleTool, Tool::Rectangle),
+        (InputAction::CircleTool, Tool::Circle)
+    ];
+
+    for (action, tool) in tool_actions.iter().copied() {
+        if input_actions.just_pressed(action) {
+            brush.tool = if brush.tool == tool { Tool::Brush } else { tool };
+        }
+    }
+
+    if input_actions.just_pressed(InputAction::CycleBrushShape) {
+        brush.shape = match brush.shape {
+            BrushShape::Circle => BrushShape::Square,
+            BrushShape::Square => BrushShape::Circle
+        };
+    }
+
+    if input_actions.just_pressed(InputAction::CycleBrushMode) {
+        brush.mode = match brush.mode {
+            BrushMode::Particles => BrushMode::Cells,
+            BrushMode::Cells => BrushMode::Particles
+        };
+    }
+
+    if input_actions.just_pressed(InputAction::ToggleEraser) {
+        brush.is_eraser = !brush.is_eraser;
+    }
+
+    if input_actions.just_pressed(InputAction::NextMaterial) {
+        let index = CellType::ALL.iter().position(|cell_type| *cell_type == brush.cell_type).unwrap_or(0);
+
+        brush.cell_type = CellType::ALL[(index + 1) % CellType::ALL.len()];
+    }
+
+    if input_actions.just_pressed(InputAction::PickMaterial) {
+        let window = windows.get_primary().unwrap();
+
+        if let (Ok(camera_transform), Ok((pixel_simulation, pixel_simulation_transform))) = (main_camera_query.single(), pixel_simulation_query.single()) {
+            let picked_cell = cursor_to_cell(window, camera_transform, pixel_simulation_transform)
+                .and_then(|cell_position| pixel_simulation.chunks.get_cell_at(WorldCellPosition(cell_position.floor().as_i32())));
+
+            if let Some(cell) = picked_cell {
+                brush.cell_type = cell.cell_type;
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,85 @@
+﻿use bevy::prelude::*;
+use bevy_egui::EguiContext;
+use crate::game::components::{MainCamera, PixelSimulation};
+use crate::game::constants::{WORLD_SIZE, CAMERA_MIN_SCALE, CAMERA_MAX_SCALE, CAMERA_ZOOM_STEP, CAMERA_KEYBOARD_PAN_SPEED};
+use crate::game::data::coordinates::{cell_to_world, screen_to_world};
+use crate::game::data::input_bindings::InputAction;
+use crate::game::resources::InputActions;
+
+pub fn update_camera(
+    mut previous_cursor_position: Local<Option<Vec2>>,
+    mut main_camera_query: Query<&mut Transform, With<MainCamera>>,
+    pixel_simulation_query: Query<&GlobalTransform, With<PixelSimulation>>,
+    egui_context: Res<EguiContext>,
+    windows: Res<Windows>,
+    time: Res<Time>,
+    input_actions: Res<InputActions>
+) {
+    let window = windows.get_primary().unwrap();
+    let mut camera_transform = match main_camera_query.single_mut() {
+        Ok(camera_transform) => camera_transform,
+        Err(_) => return
+    };
+
+    let cursor_position = window.cursor_position();
+    let is_pointer_over_ui = egui_context.ctx().wants_pointer_input();
+
+    if input_actions.pressed(InputAction::Pan) {
+        if let (Some(cursor_position), Some(previous_cursor_position)) = (cursor_position, *previous_cursor_position) {
+            let delta = (cursor_position - previous_cursor_position) * camera_transform.scale.x;
+
+            camera_transform.translation -= delta.extend(0.);
+        }
+    }
+
+    *previous_cursor_position = cursor_position;
+
+    let mut keyboard_direction = Vec2::ZERO;
+
+    if input_actions.pressed(InputAction::PanLeft) {
+        keyboard_direction.x -= 1.;
+    }
+
+    if input_actions.pressed(InputAction::PanRight) {
+        keyboard_direction.x += 1.;
+    }
+
+    if input_actions.pressed(InputAction::PanDown) {
+        keyboard_direction.y -= 1.;
+    }
+
+    if input_actions.pressed(InputAction::PanUp) {
+        keyboard_direction.y += 1.;
+    }
+
+    let keyboard_pan = keyboard_direction.normalize_or_zero() * CAMERA_KEYBOARD_PAN_SPEED * camera_transform.scale.x * time.delta_seconds();
+    camera_transform.translation += keyboard_pan.extend(0.);
+
+    let zoom_steps = input_actions.wheel_steps(InputAction::Zoom);
+
+    if !is_pointer_over_ui && zoom_steps != 0. {
+        let scale = camera_transform.scale.x;
+        let zoom_factor = CAMERA_ZOOM_STEP.powf(-zoom_steps);
+        let new_scale = (scale * zoom_factor).clamp(CAMERA_MIN_SCALE, CAMERA_MAX_SCALE);
+
+        if let Some(cursor_position) = cursor_position {
+            let zoom_origin = screen_to_world(window, &GlobalTransform::from(*camera_transform), cursor_position);
+            let offset = camera_transform.translation - zoom_origin;
+
+            camera_transform.translation = zoom_origin + offset * (new_scale / scale);
+        }
+
+        camera_transform.scale = Vec3::new(new_scale, new_scale, camera_transform.scale.z);
+    }
+
+    if let Ok(pixel_simulation_transform) = pixel_simulation_query.single() {
+        let world_size = WORLD_SIZE as f32;
+        let first_corner = cell_to_world(pixel_simulation_transform, Vec2::ZERO);
+        let second_corner = cell_to_world(pixel_simulation_transform, Vec2::ONE * world_size);
+        let min = first_corner.min(second_corner);
+        let max = first_corner.max(second_corner);
+
+        camera_transform.translation.x = camera_transform.translation.x.clamp(min.x, max.x);
+        camera_transform.translation.y = camera_transform.translation.y.clamp(min.y, max.y);
+    }
+}
\ No newline at end of file
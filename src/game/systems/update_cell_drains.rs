@@ -0,0 +1,32 @@
+﻿use bevy::prelude::*;
+use crate::game::components::{PixelSimulation, CellDrain};
+use crate::game::data::pixel_simulation::WorldCellPosition;
+use crate::game::data::coordinates::world_to_cell;
+
+pub fn update_cell_drains(
+    mut query: Query<(&mut PixelSimulation, &GlobalTransform)>,
+    cell_drain_query: Query<(&CellDrain, &GlobalTransform)>,
+    mut textures: ResMut<Assets<Texture>>
+) {
+    for (mut pixel_simulation, pixel_simulation_transform) in query.iter_mut() {
+        for (cell_drain, cell_drain_transform) in cell_drain_query.iter() {
+            let world_cell_position = world_to_cell(pixel_simulation_transform, cell_drain_transform.translation);
+            let radius = cell_drain.radius.ceil() as i32;
+            let center = world_cell_position.floor().as_i32();
+
+            for x in -radius..=radius {
+                for y in -radius..=radius {
+                    let cell_position = WorldCellPosition(center + IVec2::new(x, y));
+
+                    let cell_center = cell_position.as_f32() + Vec2::splat(0.5);
+
+                    if cell_center.distance(world_cell_position) <= cell_drain.radius && pixel_simulation.chunks.get_cell_at(cell_position).is_some() {
+                        pixel_simulation.chunks.set_cell_at(cell_position, None, &mut textures);
+                    }
+                }
+            }
+
+            pixel_simulation.particles.retain_mut(|particle| particle.position.distance(world_cell_position) > cell_drain.radius);
+        }
+    }
+}
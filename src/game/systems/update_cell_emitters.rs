@@ -0,0 +1,48 @@
+﻿use bevy::prelude::*;
+use crate::game::components::{PixelSimulation, CellEmitter};
+use crate::game::constants::{WORLD_SIZE, PIXEL_SIMULATION_TIMESTEP};
+use crate::game::data::pixel_simulation::{Particle, WorldCellPosition};
+use crate::game::data::coordinates::world_to_cell;
+use crate::game::data::cell_colors::random_cell_color;
+use crate::game::resources::SimulationRandom;
+use rand::Rng;
+
+pub fn update_cell_emitters(
+    mut query: Query<(&mut PixelSimulation, &GlobalTransform)>,
+    mut cell_emitter_query: Query<(&mut CellEmitter, &GlobalTransform)>,
+    mut simulation_random: ResMut<SimulationRandom>
+) {
+    let rng = simulation_random.rng();
+
+    for (mut cell_emitter, cell_emitter_transform) in cell_emitter_query.iter_mut() {
+        let emission_count = cell_emitter.take_emission_count(PIXEL_SIMULATION_TIMESTEP);
+
+        for (mut pixel_simulation, pixel_simulation_transform) in query.iter_mut() {
+            let world_cell_position = world_to_cell(pixel_simulation_transform, cell_emitter_transform.translation);
+
+            if !WorldCellPosition(world_cell_position.floor().as_i32()).is_in_world() {
+                continue;
+            }
+
+            for _ in 0..emission_count {
+                let angle = rng.gen_range(-0.5f32 ..= 0.5) * cell_emitter.cone_angle;
+                let (sin, cos) = angle.sin_cos();
+                let velocity = Vec2::new(
+                    cell_emitter.velocity.x * cos - cell_emitter.velocity.y * sin,
+                    cell_emitter.velocity.x * sin + cell_emitter.velocity.y * cos
+                );
+
+                let offset = Vec2::new(rng.gen_range(-1.0 ..= 1.0), rng.gen_range(-1.0 ..= 1.0)) * cell_emitter.spread;
+                let position = (world_cell_position + offset).clamp(Vec2::ZERO, Vec2::ONE * (WORLD_SIZE - 1) as f32);
+                let color = random_cell_color(cell_emitter.cell_type, rng);
+
+                pixel_simulation.particles.add_particle(Particle::new(
+                    cell_emitter.cell_type,
+                    position,
+                    velocity,
+                    color
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+﻿use bevy::prelude::*;
+use crate::game::components::{PixelSimulation, DebugOverlayPart, ChunkActivityHeatmap, ChunkDebugLabel};
+use crate::game::constants::{DEBUG_OVERLAY_HEATMAP_MAX_ALPHA, DEBUG_OVERLAY_HEATMAP_SATURATED_CELL_COUNT};
+use crate::game::data::input_bindings::InputAction;
+use crate::game::resources::{DebugOverlay, InputActions};
+
+pub fn update_debug_overlay(
+    pixel_simulation_query: Query<&PixelSimulation>,
+    mut visible_query: Query<&mut Visible, With<DebugOverlayPart>>,
+    heatmap_query: Query<(&ChunkActivityHeatmap, &Handle<ColorMaterial>)>,
+    mut label_query: Query<(&ChunkDebugLabel, &mut Text)>,
+    mut debug_overlay: ResMut<DebugOverlay>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    input_actions: Res<InputActions>
+) {
+    if input_actions.just_pressed(InputAction::ToggleDebugOverlay) {
+        debug_overlay.is_visible = !debug_overlay.is_visible;
+
+        for mut visible in visible_query.iter_mut() {
+            visible.is_visible = debug_overlay.is_visible;
+        }
+    }
+
+    if !debug_overlay.is_visible {
+        return;
+    }
+
+    if let Ok(pixel_simulation) = pixel_simulation_query.single() {
+        for (heatmap, material_handle) in heatmap_query.iter() {
+            let moved_cell_count = pixel_simulation.moved_cell_counts[*heatmap.chunk_index];
+            let heat = (moved_cell_count as f32 / DEBUG_OVERLAY_HEATMAP_SATURATED_CELL_COUNT as f32).min(1.);
+
+            if let Some(material) = materials.get_mut(material_handle) {
+                material.color.set_a(heat * DEBUG_OVERLAY_HEATMAP_MAX_ALPHA);
+            }
+        }
+
+        for (label, mut text) in label_query.iter_mut() {
+            let chunk_position = label.chunk_index.to_chunk_position();
+
+            text.sections[0].value = format!(
+                "chunk {}, {}\nmoved {}",
+                chunk_position.x,
+                chunk_position.y,
+                pixel_simulation.moved_cell_counts[*label.chunk_index]
+            );
+        }
+    }
+}
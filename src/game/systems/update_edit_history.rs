@@ -0,0 +1,47 @@
+﻿use bevy::prelude::*;
+use crate::game::components::PixelSimulation;
+use crate::game::data::input_bindings::InputAction;
+use crate::game::resources::{ActionQueue, ActionRecorder, EditHistory, InputActions};
+
+pub fn update_edit_history(
+    mut query: Query<&mut PixelSimulation>,
+    mut edit_history: ResMut<EditHistory>,
+    action_queue: Res<ActionQueue>,
+    action_recorder: Res<ActionRecorder>,
+    mut textures: ResMut<Assets<Texture>>,
+    input_actions: Res<InputActions>
+) {
+    let mut pixel_simulation = match query.single_mut() {
+        Ok(pixel_simulation) => pixel_simulation,
+        Err(_) => return
+    };
+
+    if input_actions.just_pressed(InputAction::Undo) || input_actions.just_pressed(InputAction::Redo) {
+        // Undo and redo restore cells outside of the action queue, so they would make a recording diverge.
+        if action_recorder.is_active() {
+            warn!("Undo and redo are disabled while recording or replaying");
+        } else if input_actions.just_pressed(InputAction::Undo) {
+            edit_history.undo(&mut pixel_simulation.chunks, &mut textures);
+        } else {
+            edit_history.redo(&mut pixel_simulation.chunks, &mut textures);
+        }
+
+        return;
+    }
+
+    let is_editing = input_actions.pressed(InputAction::Place)
+        || input_actions.pressed(InputAction::PlaceSecondary)
+        || input_actions.pressed(InputAction::Erase);
+    let has_started_editing = input_actions.just_pressed(InputAction::Place)
+        || input_actions.just_pressed(InputAction::PlaceSecondary)
+        || input_actions.just_pressed(InputAction::Erase);
+
+    if has_started_editing {
+        edit_history.begin_edit();
+    }
+
+    // The edit is complete once every action of the stroke has been applied.
+    if !is_editing && action_queue.pending_actions.is_empty() {
+        edit_history.end_edit();
+    }
+}
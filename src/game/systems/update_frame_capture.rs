@@ -0,0 +1,51 @@
+﻿use bevy::prelude::*;
+use crate::game::constants::{FRAME_CAPTURE_DIRECTORY, FRAME_CAPTURE_GIF_NAME};
+use crate::game::data::frame_capture::{create_capture_directory, create_gif_encoder};
+use crate::game::data::input_bindings::InputAction;
+use crate::game::resources::{FrameCapture, FrameCaptureFormat, InputActions};
+use image::ImageResult;
+use image::codecs::gif::GifEncoder;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+pub fn update_frame_capture(
+    mut frame_capture: ResMut<FrameCapture>,
+    input_actions: Res<InputActions>
+) {
+    let should_capture_gif = input_actions.just_pressed(InputAction::ToggleGifCapture);
+
+    if !input_actions.just_pressed(InputAction::ToggleFrameCapture) && !should_capture_gif {
+        return;
+    }
+
+    if !frame_capture.is_recording {
+        let format = if should_capture_gif { FrameCaptureFormat::Gif } else { FrameCaptureFormat::PngSequence };
+
+        match prepare_capture(format) {
+            Ok((directory, gif_encoder)) => {
+                info!("Started capturing a frame every {} ticks to {}", frame_capture.interval, directory.display());
+
+                frame_capture.start(format, directory, gif_encoder);
+            }
+            Err(error) => error!("Failed to start capturing to {}: {}", FRAME_CAPTURE_DIRECTORY, error)
+        }
+
+        return;
+    }
+
+    frame_capture.stop();
+
+    info!("Captured {} frames to {}", frame_capture.frame_count, frame_capture.directory.display());
+}
+
+// Every capture gets its own directory, so frames of an earlier capture never mix into a new one.
+fn prepare_capture(format: FrameCaptureFormat) -> ImageResult<(PathBuf, Option<GifEncoder<File>>)> {
+    let directory = create_capture_directory(Path::new(FRAME_CAPTURE_DIRECTORY))?;
+
+    let gif_encoder = match format {
+        FrameCaptureFormat::Gif => Some(create_gif_encoder(&directory.join(FRAME_CAPTURE_GIF_NAME))?),
+        FrameCaptureFormat::PngSequence => None
+    };
+
+    Ok((directory, gif_encoder))
+}
@@ -0,0 +1,49 @@
+﻿use bevy::prelude::*;
+use bevy::input::mouse::MouseWheel;
+use crate::game::data::input_bindings::{InputAction, InputBindings, InputSource};
+use crate::game::resources::InputActions;
+use std::collections::{HashMap, HashSet};
+
+pub fn update_input_actions(
+    mut held_actions: Local<HashMap<InputSource, Vec<InputAction>>>,
+    mut input_actions: ResMut<InputActions>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    input_bindings: Res<InputBindings>,
+    keyboard_inputs: Res<Input<KeyCode>>,
+    mouse_button_inputs: Res<Input<MouseButton>>
+) {
+    let is_control_pressed = keyboard_inputs.pressed(KeyCode::LControl) || keyboard_inputs.pressed(KeyCode::RControl);
+    let is_shift_pressed = keyboard_inputs.pressed(KeyCode::LShift) || keyboard_inputs.pressed(KeyCode::RShift);
+    let wheel_steps: f32 = mouse_wheel_events.iter()
+        .filter(|event| event.y != 0.)
+        .map(|event| event.y.signum())
+        .sum();
+
+    input_actions.clear();
+    input_actions.set_wheel_steps(wheel_steps);
+
+    let sources: HashSet<InputSource> = input_bindings.iter().map(|(_, binding)| binding.source).collect();
+
+    for source in sources {
+        let (is_pressed, is_just_pressed) = match source {
+            InputSource::Key(key_code) => (keyboard_inputs.pressed(key_code), keyboard_inputs.just_pressed(key_code)),
+            InputSource::Mouse(mouse_button) => (mouse_button_inputs.pressed(mouse_button), mouse_button_inputs.just_pressed(mouse_button)),
+            InputSource::MouseWheel => (wheel_steps != 0., wheel_steps != 0.)
+        };
+
+        if !is_pressed {
+            held_actions.remove(&source);
+            continue;
+        }
+
+        // Modifiers are only looked at when a key or button goes down, so pressing Shift or Ctrl in the middle of
+        // a stroke or while panning doesn't end it.
+        if is_just_pressed || !held_actions.contains_key(&source) {
+            held_actions.insert(source, input_bindings.resolve(source, is_control_pressed, is_shift_pressed));
+        }
+
+        for action in &held_actions[&source] {
+            input_actions.press(*action, is_just_pressed);
+        }
+    }
+}
\ No newline at end of file
@@ -1,78 +1,119 @@
 ﻿use bevy::prelude::*;
+use bevy_egui::EguiContext;
 use crate::game::components::{MainCamera, PixelSimulation};
-use crate::game::constants::CHUNK_SIZE;
-use crate::game::data::pixel_simulation::{CellType, Particle};
-use rand::Rng;
-use std::ops::Range;
-use lazy_static::lazy_static;
-use palette::{Lch, Gradient, FromColor, Srgba};
-
-lazy_static! {
-    static ref SAND_GRADIENT: Gradient<Lch> = Gradient::new(vec![
-        Lch::new(78.0, 25.0, 92.0),
-        Lch::new(83.0, 25.0, 92.0)
-    ]);
-
-    static ref WATER_GRADIENT: Gradient<Lch> = Gradient::new(vec![
-        Lch::new(65.0, 37.0, 249.0),
-        Lch::new(70.0, 37.0, 249.0)
-    ]);
-}
+use crate::game::constants::BRUSH_PARTICLE_DENSITY;
+use crate::game::data::cell_traversal::CellTraversal;
+use crate::game::data::coordinates::cursor_to_cell;
+use crate::game::data::simulation_action::SimulationAction;
+use crate::game::data::input_bindings::InputAction;
+use crate::game::resources::{ActionQueue, Brush, BrushMode, InputActions, Tool};
 
 pub fn update_pixel_simulation(
-    mut query: Query<&mut PixelSimulation>,
-    main_camera_query: Query<&Transform, With<MainCamera>>,
+    mut previous_cursor_cell_position: Local<Option<Vec2>>,
+    mut action_queue: ResMut<ActionQueue>,
+    brush: Res<Brush>,
+    egui_context: Res<EguiContext>,
+    main_camera_query: Query<&GlobalTransform, With<MainCamera>>,
+    pixel_simulation_query: Query<&GlobalTransform, With<PixelSimulation>>,
     windows: Res<Windows>,
-    mouse_button_inputs: Res<Input<MouseButton>>
+    input_actions: Res<InputActions>
 ) {
     let window = windows.get_primary().unwrap();
     let camera_transform = main_camera_query.single().unwrap();
-    let should_spawn_sand = mouse_button_inputs.pressed(MouseButton::Left);
-    let should_spawn_water = mouse_button_inputs.pressed(MouseButton::Right);
-
-    if should_spawn_sand || should_spawn_water {
-        for mut pixel_simulation in query.iter_mut() {
-            if let Some(cursor_position) = window.cursor_position() {
-                let size = Vec2::new(window.width() as f32, window.height() as f32);
-        
-                let p = cursor_position - size / 2.0;
-                let cursor_position_world = Vec2::from(camera_transform.compute_matrix() * p.extend(0.0).extend(1.0));
-        
-                let world_cell_position = (cursor_position_world / 300. * CHUNK_SIZE as f32).round() + (Vec2::ONE * (CHUNK_SIZE as f32 * 1.5));
-                let world_cell_position = Vec2::new(world_cell_position.x, 64. - world_cell_position.y);
-                let world_cell_position = Vec2::new(world_cell_position.x, world_cell_position.y + 2. * CHUNK_SIZE as f32);
-
-                const spread: Range<f32> = -10. .. 10.;
-                let mut rng = rand::thread_rng();
-
-                if should_spawn_sand {
-                    for _ in 0..5 {
-                        let color = SAND_GRADIENT.get(rng.gen_range(0.0 .. 1.0));
-                        let color = Srgba::from_color(color).into_format();
-
-                        pixel_simulation.particles.add_particle(Particle {
-                            particle_type: CellType::Sand,
-                            position: world_cell_position,
-                            velocity: Vec2::new(rng.gen_range(spread), rng.gen_range(spread)),
-                            color
-                        });
-                    }
-                }
+    let should_erase = input_actions.pressed(InputAction::Erase)
+        || (brush.is_eraser && (input_actions.pressed(InputAction::Place) || input_actions.pressed(InputAction::PlaceSecondary)));
+    let cell_type = if input_actions.pressed(InputAction::Place) {
+        Some(brush.cell_type)
+    } else if input_actions.pressed(InputAction::PlaceSecondary) {
+        Some(brush.secondary_cell_type)
+    } else {
+        None
+    };
+
+    if (!should_erase && cell_type.is_none()) || brush.tool.draw_shape().is_some() || egui_context.ctx().wants_pointer_input() {
+        *previous_cursor_cell_position = None;
+
+        return;
+    }
+
+    let pixel_simulation_transform = match pixel_simulation_query.single() {
+        Ok(pixel_simulation_transform) => pixel_simulation_transform,
+        Err(_) => return
+    };
+
+    let world_cell_position = match cursor_to_cell(window, camera_transform, pixel_simulation_transform) {
+        Some(world_cell_position) => world_cell_position.floor(),
+        None => {
+            *previous_cursor_cell_position = None;
+
+            return;
+        }
+    };
+
+    if brush.tool == Tool::Fill {
+        let has_started_filling = input_actions.just_pressed(InputAction::Place)
+            || input_actions.just_pressed(InputAction::PlaceSecondary)
+            || input_actions.just_pressed(InputAction::Erase);
+
+        if has_started_filling {
+            action_queue.pending_actions.push(SimulationAction::FloodFill {
+                cell_type: if should_erase { None } else { cell_type },
+                position: world_cell_position
+            });
+        }
 
-                if should_spawn_water {
-                    for _ in 0..5 {
-                        let color = WATER_GRADIENT.get(rng.gen_range(0.0 .. 1.0));
-                        let color = Srgba::from_color(color).into_format();
-
-                        pixel_simulation.particles.add_particle(Particle {
-                            particle_type: CellType::Water,
-                            position: world_cell_position,
-                            velocity: Vec2::new(rng.gen_range(spread), rng.gen_range(spread)),
-                            color
-                        });
-                    }
+        return;
+    }
+
+    let stamp_positions = match *previous_cursor_cell_position {
+        Some(previous_position) if previous_position != world_cell_position => {
+            let spacing = brush.radius.max(1) as f32;
+            let mut last_stamp_position = previous_position;
+            let mut stamp_positions = Vec::new();
+
+            for cell_position in CellTraversal::new(previous_position + Vec2::splat(0.5), world_cell_position + Vec2::splat(0.5)) {
+                let cell_position = cell_position.0.as_f32();
+
+                if cell_position.distance(last_stamp_position) >= spacing {
+                    stamp_positions.push(cell_position);
+                    last_stamp_position = cell_position;
                 }
             }
+
+            if last_stamp_position != world_cell_position {
+                stamp_positions.push(world_cell_position);
+            }
+
+            stamp_positions
         }
+        _ => vec![world_cell_position]
+    };
+
+    *previous_cursor_cell_position = Some(world_cell_position);
+
+    for position in stamp_positions {
+        let action = match cell_type {
+            _ if should_erase => SimulationAction::Erase {
+                position,
+                shape: brush.shape,
+                radius: brush.radius
+            },
+            Some(cell_type) if brush.mode == BrushMode::Cells => SimulationAction::PaintCells {
+                cell_type,
+                position,
+                shape: brush.shape,
+                radius: brush.radius
+            },
+            Some(cell_type) => SimulationAction::SpawnParticles {
+                cell_type,
+                position,
+                count: (brush.shape.offsets(brush.radius).count() as f32 * BRUSH_PARTICLE_DENSITY).ceil() as u32,
+                shape: brush.shape,
+                radius: brush.radius
+            },
+            None => return
+        };
+
+        action_queue.pending_actions.push(action);
     }
-}
+}
\ No newline at end of file
@@ -0,0 +1,94 @@
+﻿use bevy::prelude::*;
+use bevy_egui::EguiContext;
+use crate::game::components::{MainCamera, PixelSimulation};
+use crate::game::data::coordinates::cursor_to_cell;
+use crate::game::data::pixel_simulation::CellType;
+use crate::game::data::simulation_action::SimulationAction;
+use crate::game::data::input_bindings::InputAction;
+use crate::game::resources::{ActionQueue, Brush, InputActions, ShapePreview};
+
+pub fn update_shape_tools(
+    mut drag_start: Local<Option<(Vec2, Option<CellType>)>>,
+    mut shape_preview: ResMut<ShapePreview>,
+    mut action_queue: ResMut<ActionQueue>,
+    brush: Res<Brush>,
+    egui_context: Res<EguiContext>,
+    main_camera_query: Query<&GlobalTransform, With<MainCamera>>,
+    pixel_simulation_query: Query<&GlobalTransform, With<PixelSimulation>>,
+    windows: Res<Windows>,
+    input_actions: Res<InputActions>
+) {
+    let shape = match brush.tool.draw_shape() {
+        Some(shape) => shape,
+        None => {
+            *drag_start = None;
+
+            if shape_preview.action.is_some() {
+                shape_preview.action = None;
+            }
+
+            return;
+        }
+    };
+
+    let window = windows.get_primary().unwrap();
+    let camera_transform = main_camera_query.single().unwrap();
+    let pixel_simulation_transform = match pixel_simulation_query.single() {
+        Ok(pixel_simulation_transform) => pixel_simulation_transform,
+        Err(_) => return
+    };
+
+    let cursor_cell_position = cursor_to_cell(window, camera_transform, pixel_simulation_transform)
+        .map(|cursor_cell_position| cursor_cell_position.floor());
+
+    let has_started_drawing = input_actions.just_pressed(InputAction::Place)
+        || input_actions.just_pressed(InputAction::PlaceSecondary)
+        || input_actions.just_pressed(InputAction::Erase);
+
+    if has_started_drawing && drag_start.is_none() && !egui_context.ctx().wants_pointer_input() {
+        if let Some(cursor_cell_position) = cursor_cell_position {
+            let cell_type = if input_actions.just_pressed(InputAction::Erase) || brush.is_eraser {
+                None
+            } else if input_actions.just_pressed(InputAction::PlaceSecondary) {
+                Some(brush.secondary_cell_type)
+            } else {
+                Some(brush.cell_type)
+            };
+
+            *drag_start = Some((cursor_cell_position, cell_type));
+        }
+    }
+
+    let (start_position, cell_type) = match *drag_start {
+        Some(drag_start) => drag_start,
+        None => return
+    };
+
+    if let Some(cursor_cell_position) = cursor_cell_position {
+        let action = Some(SimulationAction::DrawShape {
+            cell_type,
+            shape,
+            from: start_position,
+            to: cursor_cell_position,
+            thickness: brush.radius,
+            is_filled: brush.is_filled
+        });
+
+        // Only touching the preview when the shape changes keeps it from being redrawn every frame.
+        if shape_preview.action != action {
+            shape_preview.action = action;
+        }
+    }
+
+    let is_drawing = input_actions.pressed(InputAction::Place)
+        || input_actions.pressed(InputAction::PlaceSecondary)
+        || input_actions.pressed(InputAction::Erase);
+
+    if !is_drawing {
+        if let Some(action) = shape_preview.action.take() {
+            action_queue.pending_actions.push(action);
+        }
+
+        *drag_start = None;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+﻿use bevy::prelude::*;
+use crate::game::constants::{MIN_SIMULATION_SPEED, MAX_SIMULATION_SPEED};
+use crate::game::data::input_bindings::InputAction;
+use crate::game::resources::{InputActions, SimulationControl};
+
+pub fn update_simulation_control(
+    input_actions: Res<InputActions>,
+    mut simulation_control: ResMut<SimulationControl>
+) {
+    if input_actions.just_pressed(InputAction::Pause) {
+        simulation_control.is_paused = !simulation_control.is_paused;
+    }
+
+    if input_actions.just_pressed(InputAction::Step) {
+        simulation_control.pending_steps += 1;
+    }
+
+    if input_actions.just_pressed(InputAction::StepMany) {
+        simulation_control.pending_steps += 10;
+    }
+
+    if input_actions.just_pressed(InputAction::SpeedUp) {
+        simulation_control.speed = (simulation_control.speed * 2.).min(MAX_SIMULATION_SPEED);
+    }
+
+    if input_actions.just_pressed(InputAction::SlowDown) {
+        simulation_control.speed = (simulation_control.speed / 2.).max(MIN_SIMULATION_SPEED);
+    }
+
+    if input_actions.just_pressed(InputAction::ResetSpeed) {
+        simulation_control.speed = 1.;
+    }
+}
\ No newline at end of file
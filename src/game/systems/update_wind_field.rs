@@ -0,0 +1,19 @@
+﻿use bevy::prelude::*;
+use crate::game::components::{PixelSimulation, WindSource};
+use crate::game::constants::WIND_FIELD_DECAY;
+use crate::game::data::coordinates::world_to_cell;
+
+pub fn update_wind_field(
+    mut query: Query<(&mut PixelSimulation, &GlobalTransform)>,
+    wind_source_query: Query<(&WindSource, &GlobalTransform)>
+) {
+    for (mut pixel_simulation, pixel_simulation_transform) in query.iter_mut() {
+        pixel_simulation.wind_field.decay(WIND_FIELD_DECAY);
+
+        for (wind_source, wind_source_transform) in wind_source_query.iter() {
+            let world_cell_position = world_to_cell(pixel_simulation_transform, wind_source_transform.translation);
+
+            pixel_simulation.wind_field.add_source(world_cell_position, wind_source.velocity, wind_source.radius);
+        }
+    }
+}
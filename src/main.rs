@@ -7,6 +7,7 @@ use bevy_inspector_egui::widgets::{ResourceInspector};
 use bevy::diagnostic::{FrameTimeDiagnosticsPlugin};
 use crate::show_fps::plugin::ShowFpsPlugin;
 use crate::game::plugin::GamePlugin;
+use crate::game::components::PixelSimulation;
 
 #[derive(Inspectable, Default)]
 struct Resources {
@@ -32,11 +33,11 @@ fn main() {
         ..Default::default()
     });
 
-    let _registry: Mut<InspectableRegistry> = app
+    let mut registry: Mut<InspectableRegistry> = app
         .world_mut()
         .get_resource_or_insert_with(InspectableRegistry::default);
 
-    // registry.register::<PixelSimulation>();
+    registry.register::<PixelSimulation>();
     
     app.run();
 }
\ No newline at end of file